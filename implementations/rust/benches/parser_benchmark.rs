@@ -1,5 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use cfgpp::{Parser, CfgppValue};
+use cfgpp::lexer::Lexer;
 
 fn benchmark_basic_parsing(c: &mut Criterion) {
     let config = r#"
@@ -55,6 +56,33 @@ fn benchmark_large_config(c: &mut Criterion) {
     });
 }
 
+fn benchmark_large_config_borrowed(c: &mut Criterion) {
+    let mut config = String::new();
+    config.push_str("root {\n");
+
+    for i in 0..1000 {
+        config.push_str(&format!(
+            r#"
+    server_{} {{
+        host = "server{}.example.com";
+        port = {};
+        enabled = true;
+        load = {}.5;
+    }}
+"#, i, i, 8000 + i, i as f64 / 10.0
+        ));
+    }
+
+    config.push_str("}\n");
+
+    c.bench_function("parse_large_config_borrowed", |b| {
+        b.iter(|| {
+            let mut parser = Parser::new();
+            parser.parse_borrowed(black_box(&config)).unwrap()
+        })
+    });
+}
+
 fn benchmark_nested_objects(c: &mut Criterion) {
     let config = r#"
     app {
@@ -169,13 +197,35 @@ fn benchmark_value_access(c: &mut Criterion) {
     });
 }
 
+fn benchmark_lexer_long_strings(c: &mut Criterion) {
+    // Long quoted strings and line comments exercise the memchr-accelerated
+    // scanning in `Lexer::read_string`/`read_line_comment`, with enough
+    // multi-byte UTF-8 mixed in to keep the byte-cursor math honest.
+    let mut config = String::new();
+    for i in 0..500 {
+        config.push_str(&format!(
+            "field_{} = \"a plain café description string number {} with no escapes\"; // trailing comment {}\n",
+            i, i, i
+        ));
+    }
+
+    c.bench_function("lex_long_strings_and_comments", |b| {
+        b.iter(|| {
+            let mut lexer = Lexer::new(black_box(&config));
+            lexer.tokenize().unwrap()
+        })
+    });
+}
+
 criterion_group!(
     benches,
     benchmark_basic_parsing,
     benchmark_large_config,
+    benchmark_large_config_borrowed,
     benchmark_nested_objects,
     benchmark_array_parsing,
     benchmark_env_var_expansion,
-    benchmark_value_access
+    benchmark_value_access,
+    benchmark_lexer_long_strings
 );
 criterion_main!(benches);
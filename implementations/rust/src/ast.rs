@@ -4,65 +4,152 @@ use crate::value::CfgppValue;
 use std::collections::HashMap;
 
 /// AST node types for CFG++ format
+///
+/// `Object` and `Expression` hold their payload behind a single [`Box`]
+/// (see [`ObjectData`]/[`ExprData`]) instead of inline - `Object`'s
+/// `HashMap` alone makes it the largest variant by far, and boxing
+/// `Expression`'s operands into one allocation instead of two separate
+/// `Box<AstNode>`s both shrink every other variant's stack footprint and
+/// cut an allocation off of each expression node. See
+/// [`tests::test_ast_node_size_is_bounded`] for the regression guard.
 #[derive(Debug, Clone, PartialEq)]
 pub enum AstNode {
     /// Root configuration object
     Root {
         objects: Vec<AstNode>,
+        span: Span,
     },
-    
+
     /// Named object definition
     Object {
-        name: Option<String>,
-        fields: HashMap<String, AstNode>,
+        data: Box<ObjectData>,
+        span: Span,
     },
-    
+
     /// Array literal
     Array {
         elements: Vec<AstNode>,
+        span: Span,
     },
-    
+
     /// Enum definition
     EnumDef {
         name: String,
         values: Vec<String>,
+        span: Span,
     },
-    
+
     /// Include directive
     Include {
         path: String,
+        span: Span,
     },
-    
+
     /// Environment variable reference
     EnvVar {
         name: String,
         default: Option<String>,
+        span: Span,
     },
-    
+
     /// Field assignment
     Assignment {
         key: String,
         value: Box<AstNode>,
+        span: Span,
     },
-    
+
     /// Literal values
     Literal {
         value: CfgppValue,
+        span: Span,
     },
-    
+
     /// Expression with operators
     Expression {
-        operator: BinaryOperator,
-        left: Box<AstNode>,
-        right: Box<AstNode>,
+        data: Box<ExprData>,
+        span: Span,
     },
-    
+
     /// Namespace reference
     Namespace {
         parts: Vec<String>,
+        span: Span,
     },
 }
 
+/// Payload of [`AstNode::Object`], boxed as a unit since its `HashMap` makes
+/// it by far the largest variant otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectData {
+    pub name: Option<String>,
+    pub fields: HashMap<String, AstNode>,
+}
+
+/// Payload of [`AstNode::Expression`], boxed as a unit so both operands
+/// share one allocation instead of each living behind its own `Box<AstNode>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExprData {
+    pub operator: BinaryOperator,
+    pub left: AstNode,
+    pub right: AstNode,
+}
+
+/// A byte-offset range `[start, end)` into the original source text that
+/// produced an [`AstNode`], for editor tooling (hover, go-to-definition,
+/// diagnostics) that needs to map a node back to its location. Every
+/// constructor (`AstNode::literal`, `AstNode::object`, ...) defaults to an
+/// empty span - attach a real one with [`AstNode::with_span`] once the
+/// parser knows where a node came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// Create a new span covering `[start, end)`.
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// An empty span, used as the default for nodes with no known location.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Whether this span is empty, i.e. carries no location information.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Whether `offset` falls within `[start, end)`.
+    pub fn contains(&self, offset: usize) -> bool {
+        self.start <= offset && offset < self.end
+    }
+}
+
+/// A problem found by [`AstNode::validate`]: structurally valid but
+/// semantically wrong, e.g. a duplicate top-level key or an enum literal
+/// that doesn't match any declared enum. Distinct from
+/// [`crate::error::CfgppError::ValidationError`] (a parse/runtime-level
+/// error) and from [`crate::schema::ValidationDiagnostic`] (a schema
+/// conformance error) - this one always carries the offending node's
+/// [`Span`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    pub message: String,
+    pub span: Span,
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
 /// Binary operators supported in CFG++
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BinaryOperator {
@@ -70,66 +157,267 @@ pub enum BinaryOperator {
     Subtract,
     Multiply,
     Divide,
+    Modulo,
+    Power,
+    Eq,
+    Ne,
+    Gt,
+    Lt,
+    Ge,
+    Le,
+    And,
+    Or,
+    /// `left ?? right` - evaluates to `left` unless it is [`CfgppValue::Null`],
+    /// in which case it evaluates to `right`.
+    Coalesce,
 }
 
 impl AstNode {
     /// Create a new root node
     pub fn root(objects: Vec<AstNode>) -> Self {
-        Self::Root { objects }
+        Self::Root { objects, span: Span::empty() }
     }
-    
+
     /// Create a new object node
     pub fn object(name: Option<String>, fields: HashMap<String, AstNode>) -> Self {
-        Self::Object { name, fields }
+        Self::Object { data: Box::new(ObjectData { name, fields }), span: Span::empty() }
     }
-    
+
     /// Create a new array node
     pub fn array(elements: Vec<AstNode>) -> Self {
-        Self::Array { elements }
+        Self::Array { elements, span: Span::empty() }
     }
-    
+
     /// Create a new literal node
     pub fn literal(value: CfgppValue) -> Self {
-        Self::Literal { value }
+        Self::Literal { value, span: Span::empty() }
     }
-    
+
     /// Create a new assignment node
     pub fn assignment(key: String, value: AstNode) -> Self {
         Self::Assignment {
             key,
             value: Box::new(value),
+            span: Span::empty(),
         }
     }
-    
+
     /// Create a new include node
     pub fn include(path: String) -> Self {
-        Self::Include { path }
+        Self::Include { path, span: Span::empty() }
     }
-    
+
     /// Create a new environment variable node
     pub fn env_var(name: String, default: Option<String>) -> Self {
-        Self::EnvVar { name, default }
+        Self::EnvVar { name, default, span: Span::empty() }
     }
-    
+
     /// Create a new enum definition node
     pub fn enum_def(name: String, values: Vec<String>) -> Self {
-        Self::EnumDef { name, values }
+        Self::EnumDef { name, values, span: Span::empty() }
     }
-    
+
     /// Create a new expression node
     pub fn expression(operator: BinaryOperator, left: AstNode, right: AstNode) -> Self {
         Self::Expression {
-            operator,
-            left: Box::new(left),
-            right: Box::new(right),
+            data: Box::new(ExprData { operator, left, right }),
+            span: Span::empty(),
         }
     }
-    
+
     /// Create a new namespace node
     pub fn namespace(parts: Vec<String>) -> Self {
-        Self::Namespace { parts }
+        Self::Namespace { parts, span: Span::empty() }
     }
-    
+
+    /// This node's location in the original source text, or an empty span
+    /// if it wasn't built from parsed source.
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Root { span, .. }
+            | Self::Object { span, .. }
+            | Self::Array { span, .. }
+            | Self::EnumDef { span, .. }
+            | Self::Include { span, .. }
+            | Self::EnvVar { span, .. }
+            | Self::Assignment { span, .. }
+            | Self::Literal { span, .. }
+            | Self::Expression { span, .. }
+            | Self::Namespace { span, .. } => *span,
+        }
+    }
+
+    /// Attach a source span to this node, overriding its default empty span.
+    pub fn with_span(mut self, span: Span) -> Self {
+        match &mut self {
+            Self::Root { span: s, .. }
+            | Self::Object { span: s, .. }
+            | Self::Array { span: s, .. }
+            | Self::EnumDef { span: s, .. }
+            | Self::Include { span: s, .. }
+            | Self::EnvVar { span: s, .. }
+            | Self::Assignment { span: s, .. }
+            | Self::Literal { span: s, .. }
+            | Self::Expression { span: s, .. }
+            | Self::Namespace { span: s, .. } => *s = span,
+        }
+        self
+    }
+
+    /// This node's immediate children, for [`AstNode::find_node_at_offset`]
+    /// to descend into.
+    fn children(&self) -> Vec<&AstNode> {
+        match self {
+            Self::Root { objects, .. } => objects.iter().collect(),
+            Self::Object { data, .. } => data.fields.values().collect(),
+            Self::Array { elements, .. } => elements.iter().collect(),
+            Self::Assignment { value, .. } => vec![value.as_ref()],
+            Self::Expression { data, .. } => vec![&data.left, &data.right],
+            Self::EnumDef { .. }
+            | Self::Include { .. }
+            | Self::EnvVar { .. }
+            | Self::Literal { .. }
+            | Self::Namespace { .. } => vec![],
+        }
+    }
+
+    /// Find the deepest (most specific) node whose span contains `offset`,
+    /// mirroring the leaf-at-offset lookup used by syntax-tree crates.
+    /// Returns `None` if this node's span doesn't contain `offset`, or if
+    /// its span is empty (unset, e.g. built programmatically rather than
+    /// parsed from source).
+    pub fn find_node_at_offset(&self, offset: usize) -> Option<&AstNode> {
+        let span = self.span();
+        if span.is_empty() || !span.contains(offset) {
+            return None;
+        }
+
+        for child in self.children() {
+            if let Some(found) = child.find_node_at_offset(offset) {
+                return Some(found);
+            }
+        }
+
+        Some(self)
+    }
+
+    /// Run structural checks the parser doesn't enforce: duplicate
+    /// top-level keys in [`AstNode::Root`], [`AstNode::Literal`] enum values
+    /// that don't match any [`AstNode::EnumDef`] declared in the tree, empty
+    /// [`AstNode::EnvVar`] names, [`AstNode::Include`] paths that are empty
+    /// or contain a `..` traversal segment, and [`AstNode::Expression`]
+    /// trees that statically divide or modulo by zero. Collects every
+    /// problem in one traversal, in source order, instead of stopping at
+    /// the first.
+    ///
+    /// `Object.fields` is a `HashMap`, so a duplicate key there was already
+    /// collapsed before it reached the AST - only `Root`'s list of
+    /// top-level objects/assignments can still carry one.
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        let enum_values = self.collect_enum_values();
+        self.validate_into(&enum_values, &mut errors);
+        errors.sort_by_key(|error| error.span.start);
+        errors
+    }
+
+    fn collect_enum_values(&self) -> std::collections::HashSet<&str> {
+        let mut values = std::collections::HashSet::new();
+        self.collect_enum_values_into(&mut values);
+        values
+    }
+
+    fn collect_enum_values_into<'a>(&'a self, values: &mut std::collections::HashSet<&'a str>) {
+        if let Self::EnumDef { values: declared, .. } = self {
+            values.extend(declared.iter().map(String::as_str));
+        }
+        for child in self.children() {
+            child.collect_enum_values_into(values);
+        }
+    }
+
+    fn validate_into(&self, enum_values: &std::collections::HashSet<&str>, errors: &mut Vec<ValidationError>) {
+        match self {
+            Self::Root { objects, .. } => {
+                let mut seen = HashMap::new();
+                for object in objects {
+                    if let Some(key) = Self::top_level_key(object) {
+                        if seen.insert(key.to_string(), ()).is_some() {
+                            errors.push(ValidationError {
+                                message: format!("Duplicate top-level key '{}'", key),
+                                span: object.span(),
+                            });
+                        }
+                    }
+                }
+            }
+
+            Self::EnvVar { name, span, .. } => {
+                if name.is_empty() {
+                    errors.push(ValidationError {
+                        message: "EnvVar reference has an empty name".to_string(),
+                        span: *span,
+                    });
+                }
+            }
+
+            Self::Include { path, span } => {
+                if path.is_empty() {
+                    errors.push(ValidationError { message: "Include path is empty".to_string(), span: *span });
+                } else if path.split(['/', '\\']).any(|segment| segment == "..") {
+                    errors.push(ValidationError {
+                        message: format!("Include path '{}' contains a '..' traversal segment", path),
+                        span: *span,
+                    });
+                }
+            }
+
+            Self::Literal { value, span } => {
+                if let CfgppValue::Enum(variant) = value {
+                    if !enum_values.is_empty() && !enum_values.contains(variant.as_str()) {
+                        errors.push(ValidationError {
+                            message: format!("Enum value '{}' does not match any declared enum", variant),
+                            span: *span,
+                        });
+                    }
+                }
+            }
+
+            Self::Expression { data, span } => {
+                if matches!(data.operator, BinaryOperator::Divide | BinaryOperator::Modulo)
+                    && Self::is_statically_zero(&data.right)
+                {
+                    errors.push(ValidationError {
+                        message: format!("Expression statically {} by zero", if matches!(data.operator, BinaryOperator::Divide) { "divides" } else { "modulos" }),
+                        span: *span,
+                    });
+                }
+            }
+
+            Self::Object { .. } | Self::EnumDef { .. } | Self::Assignment { .. } | Self::Array { .. } | Self::Namespace { .. } => {}
+        }
+
+        for child in self.children() {
+            child.validate_into(enum_values, errors);
+        }
+    }
+
+    fn top_level_key(node: &AstNode) -> Option<&str> {
+        match node {
+            Self::Object { data, .. } => data.name.as_deref(),
+            Self::Assignment { key, .. } => Some(key.as_str()),
+            _ => None,
+        }
+    }
+
+    fn is_statically_zero(node: &AstNode) -> bool {
+        match node.to_value() {
+            Ok(CfgppValue::Integer(0)) | Ok(CfgppValue::UInteger(0)) => true,
+            Ok(CfgppValue::Double(d)) => d == 0.0,
+            _ => false,
+        }
+    }
+
     /// Get the type name of this AST node
     pub fn type_name(&self) -> &'static str {
         match self {
@@ -169,7 +457,7 @@ impl AstNode {
     /// Get the literal value if this is a literal node
     pub fn as_literal(&self) -> Option<&CfgppValue> {
         match self {
-            Self::Literal { value } => Some(value),
+            Self::Literal { value, .. } => Some(value),
             _ => None,
         }
     }
@@ -177,7 +465,7 @@ impl AstNode {
     /// Get the object fields if this is an object node
     pub fn as_object(&self) -> Option<&HashMap<String, AstNode>> {
         match self {
-            Self::Object { fields, .. } => Some(fields),
+            Self::Object { data, .. } => Some(&data.fields),
             _ => None,
         }
     }
@@ -185,7 +473,7 @@ impl AstNode {
     /// Get the array elements if this is an array node
     pub fn as_array(&self) -> Option<&Vec<AstNode>> {
         match self {
-            Self::Array { elements } => Some(elements),
+            Self::Array { elements, .. } => Some(elements),
             _ => None,
         }
     }
@@ -193,17 +481,17 @@ impl AstNode {
     /// Convert this AST node to a CFG++ value
     pub fn to_value(&self) -> crate::error::CfgppResult<CfgppValue> {
         match self {
-            Self::Literal { value } => Ok(value.clone()),
+            Self::Literal { value, .. } => Ok(value.clone()),
             
-            Self::Object { fields, .. } => {
-                let mut object = HashMap::new();
-                for (key, node) in fields {
+            Self::Object { data, .. } => {
+                let mut object = crate::value::CfgppObject::new();
+                for (key, node) in &data.fields {
                     object.insert(key.clone(), node.to_value()?);
                 }
                 Ok(CfgppValue::object_with_values(object))
             }
             
-            Self::Array { elements } => {
+            Self::Array { elements, .. } => {
                 let mut array = Vec::new();
                 for element in elements {
                     array.push(element.to_value()?);
@@ -212,17 +500,23 @@ impl AstNode {
             }
             
             Self::Assignment { value, .. } => value.to_value(),
-            
-            Self::Root { objects } => {
-                let mut root_object = HashMap::new();
+
+            Self::Expression { data, .. } => {
+                eval_binary(&data.operator, data.left.to_value()?, data.right.to_value()?)
+            }
+
+            Self::Root { objects, .. } => {
+                let mut root_object = crate::value::CfgppObject::new();
                 for obj in objects {
-                    if let Self::Object { name: Some(name), fields } = obj {
-                        let mut object = HashMap::new();
-                        for (key, node) in fields {
-                            object.insert(key.clone(), node.to_value()?);
+                    if let Self::Object { data, .. } = obj {
+                        if let Some(name) = &data.name {
+                            let mut object = crate::value::CfgppObject::new();
+                            for (key, node) in &data.fields {
+                                object.insert(key.clone(), node.to_value()?);
+                            }
+                            root_object.insert(name.clone(), CfgppValue::object_with_values(object));
                         }
-                        root_object.insert(name.clone(), CfgppValue::object_with_values(object));
-                    } else if let Self::Assignment { key, value } = obj {
+                    } else if let Self::Assignment { key, value, .. } = obj {
                         root_object.insert(key.clone(), value.to_value()?);
                     }
                 }
@@ -240,7 +534,7 @@ impl AstNode {
         let spacing = "  ".repeat(indent);
         
         match self {
-            Self::Root { objects } => {
+            Self::Root { objects, .. } => {
                 let mut result = format!("{}Root {{\n", spacing);
                 for obj in objects {
                     result.push_str(&obj.pretty_print(indent + 1));
@@ -249,14 +543,14 @@ impl AstNode {
                 result
             }
             
-            Self::Object { name, fields } => {
-                let mut result = if let Some(name) = name {
+            Self::Object { data, .. } => {
+                let mut result = if let Some(name) = &data.name {
                     format!("{}Object {} {{\n", spacing, name)
                 } else {
                     format!("{}Object {{\n", spacing)
                 };
-                
-                for (key, node) in fields {
+
+                for (key, node) in &data.fields {
                     result.push_str(&format!("{}  {}: ", spacing, key));
                     if node.is_literal() {
                         result.push_str(&format!("{}\n", node.as_literal().unwrap()));
@@ -269,7 +563,7 @@ impl AstNode {
                 result
             }
             
-            Self::Array { elements } => {
+            Self::Array { elements, .. } => {
                 let mut result = format!("{}Array [\n", spacing);
                 for element in elements {
                     result.push_str(&element.pretty_print(indent + 1));
@@ -278,11 +572,11 @@ impl AstNode {
                 result
             }
             
-            Self::Literal { value } => {
+            Self::Literal { value, .. } => {
                 format!("{}Literal({})\n", spacing, value)
             }
             
-            Self::Assignment { key, value } => {
+            Self::Assignment { key, value, .. } => {
                 let mut result = format!("{}Assignment {} = ", spacing, key);
                 if value.is_literal() {
                     result.push_str(&format!("{}\n", value.as_literal().unwrap()));
@@ -293,11 +587,11 @@ impl AstNode {
                 result
             }
             
-            Self::Include { path } => {
+            Self::Include { path, .. } => {
                 format!("{}Include \"{}\"\n", spacing, path)
             }
             
-            Self::EnvVar { name, default } => {
+            Self::EnvVar { name, default, .. } => {
                 if let Some(default) = default {
                     format!("{}EnvVar ${{{}:-{}}}\n", spacing, name, default)
                 } else {
@@ -305,26 +599,37 @@ impl AstNode {
                 }
             }
             
-            Self::EnumDef { name, values } => {
+            Self::EnumDef { name, values, .. } => {
                 format!("{}EnumDef {} {{ {} }}\n", spacing, name, values.join(", "))
             }
             
-            Self::Expression { operator, left, right } => {
-                let op_str = match operator {
+            Self::Expression { data, .. } => {
+                let op_str = match &data.operator {
                     BinaryOperator::Add => "+",
                     BinaryOperator::Subtract => "-",
                     BinaryOperator::Multiply => "*",
                     BinaryOperator::Divide => "/",
+                    BinaryOperator::Modulo => "%",
+                    BinaryOperator::Power => "**",
+                    BinaryOperator::Eq => "==",
+                    BinaryOperator::Ne => "!=",
+                    BinaryOperator::Gt => ">",
+                    BinaryOperator::Lt => "<",
+                    BinaryOperator::Ge => ">=",
+                    BinaryOperator::Le => "<=",
+                    BinaryOperator::And => "&&",
+                    BinaryOperator::Or => "||",
+                    BinaryOperator::Coalesce => "??",
                 };
                 
                 let mut result = format!("{}Expression {} {{\n", spacing, op_str);
-                result.push_str(&left.pretty_print(indent + 1));
-                result.push_str(&right.pretty_print(indent + 1));
+                result.push_str(&data.left.pretty_print(indent + 1));
+                result.push_str(&data.right.pretty_print(indent + 1));
                 result.push_str(&format!("{}}}\n", spacing));
                 result
             }
             
-            Self::Namespace { parts } => {
+            Self::Namespace { parts, .. } => {
                 format!("{}Namespace {}\n", spacing, parts.join("::"))
             }
         }
@@ -337,10 +642,185 @@ impl std::fmt::Display for AstNode {
     }
 }
 
+/// A numeric `CfgppValue` operand, tracking whether it started out as an
+/// integer or a float so arithmetic can decide whether to stay integer or
+/// promote to [`CfgppValue::Double`].
+enum Numeric {
+    Int(i64),
+    Float(f64),
+}
+
+impl Numeric {
+    fn as_f64(&self) -> f64 {
+        match self {
+            Self::Int(i) => *i as f64,
+            Self::Float(f) => *f,
+        }
+    }
+}
+
+fn as_numeric(value: &CfgppValue) -> Option<Numeric> {
+    match value {
+        CfgppValue::Integer(i) => Some(Numeric::Int(*i)),
+        CfgppValue::Double(d) => Some(Numeric::Float(*d)),
+        _ => None,
+    }
+}
+
+/// Evaluate a [`BinaryOperator`] applied to two already-evaluated operands.
+fn eval_binary(operator: &BinaryOperator, left: CfgppValue, right: CfgppValue) -> crate::error::CfgppResult<CfgppValue> {
+    match operator {
+        BinaryOperator::Coalesce => Ok(if left.is_null() { right } else { left }),
+
+        BinaryOperator::Eq => Ok(CfgppValue::boolean(left == right)),
+        BinaryOperator::Ne => Ok(CfgppValue::boolean(left != right)),
+
+        BinaryOperator::And | BinaryOperator::Or => eval_logical(operator, &left, &right),
+
+        BinaryOperator::Gt | BinaryOperator::Lt | BinaryOperator::Ge | BinaryOperator::Le => {
+            eval_comparison(operator, &left, &right)
+        }
+
+        BinaryOperator::Add
+        | BinaryOperator::Subtract
+        | BinaryOperator::Multiply
+        | BinaryOperator::Divide
+        | BinaryOperator::Modulo
+        | BinaryOperator::Power => {
+            if let (BinaryOperator::Add, CfgppValue::String(a), CfgppValue::String(b)) = (operator, &left, &right) {
+                return Ok(CfgppValue::string(format!("{}{}", a, b)));
+            }
+
+            let (Some(l), Some(r)) = (as_numeric(&left), as_numeric(&right)) else {
+                return Err(crate::error::CfgppError::parse_error(format!(
+                    "Cannot apply operator {:?} to {} and {}",
+                    operator,
+                    left.type_name(),
+                    right.type_name()
+                )));
+            };
+            eval_arithmetic(operator, l, r)
+        }
+    }
+}
+
+fn eval_logical(operator: &BinaryOperator, left: &CfgppValue, right: &CfgppValue) -> crate::error::CfgppResult<CfgppValue> {
+    let (Some(a), Some(b)) = (left.as_boolean(), right.as_boolean()) else {
+        return Err(crate::error::CfgppError::parse_error(format!(
+            "Operator {:?} requires boolean operands, found {} and {}",
+            operator,
+            left.type_name(),
+            right.type_name()
+        )));
+    };
+
+    Ok(CfgppValue::boolean(match operator {
+        BinaryOperator::And => a && b,
+        BinaryOperator::Or => a || b,
+        _ => unreachable!("eval_logical only handles And/Or"),
+    }))
+}
+
+fn eval_comparison(operator: &BinaryOperator, left: &CfgppValue, right: &CfgppValue) -> crate::error::CfgppResult<CfgppValue> {
+    let ordering = if let (Some(a), Some(b)) = (as_numeric(left), as_numeric(right)) {
+        a.as_f64().partial_cmp(&b.as_f64())
+    } else if let (Some(a), Some(b)) = (left.as_string(), right.as_string()) {
+        Some(a.cmp(b))
+    } else {
+        None
+    };
+
+    let Some(ordering) = ordering else {
+        return Err(crate::error::CfgppError::parse_error(format!(
+            "Cannot compare {} and {} with operator {:?}",
+            left.type_name(),
+            right.type_name(),
+            operator
+        )));
+    };
+
+    use std::cmp::Ordering;
+    Ok(CfgppValue::boolean(match operator {
+        BinaryOperator::Gt => ordering == Ordering::Greater,
+        BinaryOperator::Lt => ordering == Ordering::Less,
+        BinaryOperator::Ge => ordering != Ordering::Less,
+        BinaryOperator::Le => ordering != Ordering::Greater,
+        _ => unreachable!("eval_comparison only handles Gt/Lt/Ge/Le"),
+    }))
+}
+
+fn eval_arithmetic(operator: &BinaryOperator, left: Numeric, right: Numeric) -> crate::error::CfgppResult<CfgppValue> {
+    if let (BinaryOperator::Divide, Numeric::Int(a), Numeric::Int(b)) = (operator, &left, &right) {
+        if *b == 0 {
+            return Err(crate::error::CfgppError::parse_error("Division by zero in expression"));
+        }
+        return Ok(if a % b == 0 {
+            CfgppValue::integer(a / b)
+        } else {
+            CfgppValue::Double(*a as f64 / *b as f64)
+        });
+    }
+
+    if let (BinaryOperator::Modulo, Numeric::Int(a), Numeric::Int(b)) = (operator, &left, &right) {
+        if *b == 0 {
+            return Err(crate::error::CfgppError::parse_error("Modulo by zero in expression"));
+        }
+        return Ok(CfgppValue::integer(a % b));
+    }
+
+    if let (BinaryOperator::Power, Numeric::Int(a), Numeric::Int(b)) = (operator, &left, &right) {
+        if *b >= 0 {
+            return Ok(CfgppValue::integer(a.pow(*b as u32)));
+        }
+    }
+
+    match (operator, left, right) {
+        (BinaryOperator::Add, Numeric::Int(a), Numeric::Int(b)) => Ok(CfgppValue::integer(a + b)),
+        (BinaryOperator::Subtract, Numeric::Int(a), Numeric::Int(b)) => Ok(CfgppValue::integer(a - b)),
+        (BinaryOperator::Multiply, Numeric::Int(a), Numeric::Int(b)) => Ok(CfgppValue::integer(a * b)),
+        (operator, a, b) => {
+            let (a, b) = (a.as_f64(), b.as_f64());
+            match operator {
+                BinaryOperator::Add => Ok(CfgppValue::Double(a + b)),
+                BinaryOperator::Subtract => Ok(CfgppValue::Double(a - b)),
+                BinaryOperator::Multiply => Ok(CfgppValue::Double(a * b)),
+                BinaryOperator::Divide => {
+                    if b == 0.0 {
+                        Err(crate::error::CfgppError::parse_error("Division by zero in expression"))
+                    } else {
+                        Ok(CfgppValue::Double(a / b))
+                    }
+                }
+                BinaryOperator::Modulo => {
+                    if b == 0.0 {
+                        Err(crate::error::CfgppError::parse_error("Modulo by zero in expression"))
+                    } else {
+                        Ok(CfgppValue::Double(a % b))
+                    }
+                }
+                BinaryOperator::Power => Ok(CfgppValue::Double(a.powf(b))),
+                _ => unreachable!("eval_arithmetic only handles arithmetic operators"),
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_ast_node_size_is_bounded() {
+        // `Object`'s `HashMap` and `Expression`'s two operands are boxed
+        // behind `ObjectData`/`ExprData` precisely so this doesn't regress
+        // back toward the size of the heaviest inline variant.
+        assert!(
+            std::mem::size_of::<AstNode>() <= 80,
+            "AstNode grew to {} bytes - did a variant stop being boxed?",
+            std::mem::size_of::<AstNode>()
+        );
+    }
+
     #[test]
     fn test_ast_construction() {
         let mut fields = HashMap::new();
@@ -364,4 +844,268 @@ mod tests {
         assert!(output.contains("Literal"));
         assert!(output.contains("test"));
     }
+
+    fn expr(operator: BinaryOperator, left: CfgppValue, right: CfgppValue) -> AstNode {
+        AstNode::expression(operator, AstNode::literal(left), AstNode::literal(right))
+    }
+
+    #[test]
+    fn test_integer_arithmetic_stays_integer() {
+        let value = expr(BinaryOperator::Add, CfgppValue::integer(2), CfgppValue::integer(3)).to_value().unwrap();
+        assert_eq!(value, CfgppValue::integer(5));
+    }
+
+    #[test]
+    fn test_exact_integer_division_stays_integer() {
+        let value = expr(BinaryOperator::Divide, CfgppValue::integer(6), CfgppValue::integer(3)).to_value().unwrap();
+        assert_eq!(value, CfgppValue::integer(2));
+    }
+
+    #[test]
+    fn test_inexact_integer_division_promotes_to_float() {
+        let value = expr(BinaryOperator::Divide, CfgppValue::integer(7), CfgppValue::integer(2)).to_value().unwrap();
+        assert_eq!(value, CfgppValue::Double(3.5));
+    }
+
+    #[test]
+    fn test_mixing_float_promotes_result() {
+        let value = expr(BinaryOperator::Add, CfgppValue::integer(1), CfgppValue::Double(0.5)).to_value().unwrap();
+        assert_eq!(value, CfgppValue::Double(1.5));
+    }
+
+    #[test]
+    fn test_division_by_zero_is_a_parse_error() {
+        let result = expr(BinaryOperator::Divide, CfgppValue::integer(1), CfgppValue::integer(0)).to_value();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_modulo_by_zero_is_a_parse_error() {
+        let result = expr(BinaryOperator::Modulo, CfgppValue::integer(1), CfgppValue::integer(0)).to_value();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_integer_power() {
+        let value = expr(BinaryOperator::Power, CfgppValue::integer(2), CfgppValue::integer(10)).to_value().unwrap();
+        assert_eq!(value, CfgppValue::integer(1024));
+    }
+
+    #[test]
+    fn test_string_concatenation() {
+        let value =
+            expr(BinaryOperator::Add, CfgppValue::string("foo"), CfgppValue::string("bar")).to_value().unwrap();
+        assert_eq!(value, CfgppValue::string("foobar"));
+    }
+
+    #[test]
+    fn test_comparison_operators() {
+        assert_eq!(
+            expr(BinaryOperator::Gt, CfgppValue::integer(5), CfgppValue::integer(3)).to_value().unwrap(),
+            CfgppValue::boolean(true)
+        );
+        assert_eq!(
+            expr(BinaryOperator::Le, CfgppValue::integer(5), CfgppValue::integer(5)).to_value().unwrap(),
+            CfgppValue::boolean(true)
+        );
+    }
+
+    #[test]
+    fn test_logical_operators() {
+        assert_eq!(
+            expr(BinaryOperator::And, CfgppValue::boolean(true), CfgppValue::boolean(false)).to_value().unwrap(),
+            CfgppValue::boolean(false)
+        );
+        assert_eq!(
+            expr(BinaryOperator::Or, CfgppValue::boolean(true), CfgppValue::boolean(false)).to_value().unwrap(),
+            CfgppValue::boolean(true)
+        );
+    }
+
+    #[test]
+    fn test_coalesce_returns_right_only_when_left_is_null() {
+        assert_eq!(
+            expr(BinaryOperator::Coalesce, CfgppValue::null(), CfgppValue::integer(7)).to_value().unwrap(),
+            CfgppValue::integer(7)
+        );
+        assert_eq!(
+            expr(BinaryOperator::Coalesce, CfgppValue::integer(1), CfgppValue::integer(7)).to_value().unwrap(),
+            CfgppValue::integer(1)
+        );
+    }
+
+    #[test]
+    fn test_eq_ne_operators() {
+        assert_eq!(
+            expr(BinaryOperator::Eq, CfgppValue::integer(1), CfgppValue::integer(1)).to_value().unwrap(),
+            CfgppValue::boolean(true)
+        );
+        assert_eq!(
+            expr(BinaryOperator::Ne, CfgppValue::string("a"), CfgppValue::string("b")).to_value().unwrap(),
+            CfgppValue::boolean(true)
+        );
+    }
+
+    #[test]
+    fn test_new_nodes_default_to_an_empty_span() {
+        let node = AstNode::literal(CfgppValue::integer(1));
+        assert_eq!(node.span(), Span::empty());
+        assert!(node.span().is_empty());
+    }
+
+    #[test]
+    fn test_find_node_at_offset_returns_deepest_enclosing_node() {
+        let host = AstNode::literal(CfgppValue::string("localhost")).with_span(Span::new(7, 18));
+        let port = AstNode::literal(CfgppValue::integer(5432)).with_span(Span::new(25, 29));
+        let mut fields = HashMap::new();
+        fields.insert("host".to_string(), host);
+        fields.insert("port".to_string(), port);
+        let object = AstNode::object(Some("database".to_string()), fields).with_span(Span::new(0, 30));
+        let root = AstNode::root(vec![object]).with_span(Span::new(0, 30));
+
+        let found = root.find_node_at_offset(10).unwrap();
+        assert_eq!(found.as_literal().and_then(CfgppValue::as_string), Some("localhost"));
+
+        let found = root.find_node_at_offset(27).unwrap();
+        assert_eq!(found.as_literal().and_then(CfgppValue::as_integer), Some(5432));
+    }
+
+    #[test]
+    fn test_find_node_at_offset_prefers_the_strictly_containing_sibling() {
+        let first = AstNode::literal(CfgppValue::integer(1)).with_span(Span::new(0, 5));
+        let second = AstNode::literal(CfgppValue::integer(2)).with_span(Span::new(5, 10));
+        let root = AstNode::array(vec![first, second]).with_span(Span::new(0, 10));
+
+        let found = root.find_node_at_offset(5).unwrap();
+        assert_eq!(found.as_literal().and_then(CfgppValue::as_integer), Some(2));
+    }
+
+    #[test]
+    fn test_find_node_at_offset_is_none_when_spans_are_unset() {
+        let root = AstNode::root(vec![AstNode::literal(CfgppValue::integer(1))]);
+        assert!(root.find_node_at_offset(0).is_none());
+    }
+
+    #[test]
+    fn test_find_node_at_offset_is_none_outside_the_root_span() {
+        let root = AstNode::root(vec![]).with_span(Span::new(0, 10));
+        assert!(root.find_node_at_offset(50).is_none());
+    }
+
+    #[test]
+    fn test_validate_flags_duplicate_top_level_keys() {
+        let root = AstNode::root(vec![
+            AstNode::assignment("port".to_string(), AstNode::literal(CfgppValue::integer(1))).with_span(Span::new(0, 5)),
+            AstNode::assignment("port".to_string(), AstNode::literal(CfgppValue::integer(2))).with_span(Span::new(5, 10)),
+        ]);
+
+        let errors = root.validate();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("port"));
+        assert_eq!(errors[0].span, Span::new(5, 10));
+    }
+
+    #[test]
+    fn test_validate_allows_distinct_top_level_keys() {
+        let root = AstNode::root(vec![
+            AstNode::assignment("host".to_string(), AstNode::literal(CfgppValue::string("localhost"))),
+            AstNode::assignment("port".to_string(), AstNode::literal(CfgppValue::integer(1))),
+        ]);
+
+        assert!(root.validate().is_empty());
+    }
+
+    #[test]
+    fn test_validate_flags_enum_literal_not_in_any_declared_enum() {
+        let root = AstNode::root(vec![
+            AstNode::enum_def("Level".to_string(), vec!["low".to_string(), "high".to_string()]),
+            AstNode::assignment("level".to_string(), AstNode::literal(CfgppValue::Enum("medium".to_string())))
+                .with_span(Span::new(0, 1)),
+        ]);
+
+        let errors = root.validate();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("medium"));
+    }
+
+    #[test]
+    fn test_validate_ignores_enum_literals_when_no_enum_is_declared() {
+        let root =
+            AstNode::root(vec![AstNode::literal(CfgppValue::Enum("whatever".to_string()))]);
+
+        assert!(root.validate().is_empty());
+    }
+
+    #[test]
+    fn test_validate_flags_empty_env_var_name() {
+        let node = AstNode::env_var(String::new(), None).with_span(Span::new(2, 4));
+
+        let errors = node.validate();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].span, Span::new(2, 4));
+    }
+
+    #[test]
+    fn test_validate_flags_empty_and_traversal_include_paths() {
+        let root = AstNode::root(vec![
+            AstNode::include(String::new()),
+            AstNode::include("../secrets.cfgpp".to_string()),
+            AstNode::include("base.cfgpp".to_string()),
+        ]);
+
+        let errors = root.validate();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].message.contains("empty"));
+        assert!(errors[1].message.contains(".."));
+    }
+
+    #[test]
+    fn test_validate_flags_statically_zero_divisor() {
+        let divide = AstNode::expression(
+            BinaryOperator::Divide,
+            AstNode::literal(CfgppValue::integer(10)),
+            AstNode::literal(CfgppValue::integer(0)),
+        )
+        .with_span(Span::new(0, 6));
+        let modulo = AstNode::expression(
+            BinaryOperator::Modulo,
+            AstNode::literal(CfgppValue::integer(10)),
+            AstNode::literal(CfgppValue::integer(0)),
+        );
+        let root = AstNode::root(vec![divide, modulo]);
+
+        let errors = root.validate();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].span, Span::new(0, 6));
+    }
+
+    #[test]
+    fn test_validate_allows_nonzero_and_dynamic_divisors() {
+        let divide = AstNode::expression(
+            BinaryOperator::Divide,
+            AstNode::literal(CfgppValue::integer(10)),
+            AstNode::literal(CfgppValue::integer(2)),
+        );
+        let dynamic = AstNode::expression(
+            BinaryOperator::Divide,
+            AstNode::literal(CfgppValue::integer(10)),
+            AstNode::env_var("DIVISOR".to_string(), None),
+        );
+        let root = AstNode::root(vec![divide, dynamic]);
+
+        assert!(root.validate().is_empty());
+    }
+
+    #[test]
+    fn test_validate_collects_every_error_in_one_pass() {
+        let root = AstNode::root(vec![
+            AstNode::assignment("dup".to_string(), AstNode::literal(CfgppValue::integer(1))),
+            AstNode::assignment("dup".to_string(), AstNode::literal(CfgppValue::integer(2))),
+            AstNode::include(String::new()),
+            AstNode::env_var(String::new(), None),
+        ]);
+
+        let errors = root.validate();
+        assert_eq!(errors.len(), 3);
+    }
 }
@@ -0,0 +1,148 @@
+//! Zero-copy value tree that borrows strings and keys directly from the
+//! source buffer instead of allocating a fresh `String` for every scalar and
+//! object key, the way [`crate::value::CfgppValue`] does.
+//!
+//! Produced by [`crate::parser::Parser::parse_borrowed`]. A string or
+//! identifier only allocates when its source text isn't already usable
+//! as-is - currently, that means a quoted string containing an escape
+//! sequence, or any triple-quoted string (its body is dedented). Everything
+//! else (bare identifiers, unescaped and raw strings, object keys) is a
+//! [`Cow::Borrowed`] slice of the input.
+
+use crate::value::{CfgppObject, CfgppValue};
+use std::borrow::Cow;
+
+/// A CFG++ value whose strings and object keys borrow from the input buffer
+/// of lifetime `'a` wherever possible.
+///
+/// Mirrors [`CfgppValue`]'s scalar/container shape; it intentionally leaves
+/// out `Raw`, `BigNumber`, `SizedInteger`, and the include/env-var directives
+/// handled during owned parsing, since those either require ownership of
+/// generated text or aren't on the hot path this type exists to speed up. Use
+/// [`BorrowedValue::to_owned`] once the borrowed data needs to outlive the
+/// input or go through the full owned pipeline.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BorrowedValue<'a> {
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    UInteger(u64),
+    Double(f64),
+    String(Cow<'a, str>),
+    Enum(Cow<'a, str>),
+    Array(Vec<BorrowedValue<'a>>),
+    Object(Vec<(Cow<'a, str>, BorrowedValue<'a>)>),
+}
+
+impl<'a> BorrowedValue<'a> {
+    /// Look up a field by key on an `Object` value.
+    pub fn get(&self, key: &str) -> Option<&BorrowedValue<'a>> {
+        match self {
+            Self::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Get this value as a string, if it is a `String` or `Enum`.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) | Self::Enum(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// True if any string or key in this tree had to allocate (i.e. came
+    /// from a `Cow::Owned`), rather than borrowing from the input.
+    pub fn has_owned_strings(&self) -> bool {
+        match self {
+            Self::String(s) | Self::Enum(s) => matches!(s, Cow::Owned(_)),
+            Self::Array(items) => items.iter().any(Self::has_owned_strings),
+            Self::Object(fields) => fields.iter().any(|(k, v)| {
+                matches!(k, Cow::Owned(_)) || v.has_owned_strings()
+            }),
+            _ => false,
+        }
+    }
+
+    /// Convert this borrowed tree into a fully owned [`CfgppValue`], cloning
+    /// every still-borrowed string.
+    pub fn to_owned(&self) -> CfgppValue {
+        match self {
+            Self::Null => CfgppValue::Null,
+            Self::Boolean(b) => CfgppValue::Boolean(*b),
+            Self::Integer(i) => CfgppValue::Integer(*i),
+            Self::UInteger(u) => CfgppValue::UInteger(*u),
+            Self::Double(d) => CfgppValue::Double(*d),
+            Self::String(s) => CfgppValue::String(s.to_string()),
+            Self::Enum(s) => CfgppValue::Enum(s.to_string()),
+            Self::Array(items) => {
+                CfgppValue::array_with_values(items.iter().map(Self::to_owned).collect())
+            }
+            Self::Object(fields) => {
+                let mut object = CfgppObject::new();
+                for (key, value) in fields {
+                    object.insert(key.to_string(), value.to_owned());
+                }
+                CfgppValue::object_with_values(object)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_unescaped_strings_and_keys_borrow_from_input() {
+        let input = r#"root { host = "localhost"; port = 5432; }"#;
+        let mut parser = Parser::new();
+        let value = parser.parse_borrowed(input).unwrap();
+
+        assert!(!value.has_owned_strings());
+        assert_eq!(value.get("host").and_then(BorrowedValue::as_str), Some("localhost"));
+        assert_eq!(value.get("port"), Some(&BorrowedValue::Integer(5432)));
+    }
+
+    #[test]
+    fn test_escaped_strings_allocate_but_still_parse_correctly() {
+        let input = r#"root { greeting = "hi\nthere"; }"#;
+        let mut parser = Parser::new();
+        let value = parser.parse_borrowed(input).unwrap();
+
+        assert!(value.has_owned_strings());
+        assert_eq!(value.get("greeting").and_then(BorrowedValue::as_str), Some("hi\nthere"));
+    }
+
+    #[test]
+    fn test_raw_string_borrows_from_input() {
+        let input = r#"root { path = r"C:\no\escapes"; }"#;
+        let mut parser = Parser::new();
+        let value = parser.parse_borrowed(input).unwrap();
+
+        assert!(!value.has_owned_strings());
+        assert_eq!(value.get("path").and_then(BorrowedValue::as_str), Some(r"C:\no\escapes"));
+    }
+
+    #[test]
+    fn test_triple_quoted_string_always_allocates() {
+        let input = "root { note = \"\"\"\n    hi\n    \"\"\"; }";
+        let mut parser = Parser::new();
+        let value = parser.parse_borrowed(input).unwrap();
+
+        assert!(value.has_owned_strings());
+        assert_eq!(value.get("note").and_then(BorrowedValue::as_str), Some("hi\n"));
+    }
+
+    #[test]
+    fn test_to_owned_round_trips_through_cfgpp_value() {
+        let input = r#"root { host = "localhost"; enabled = true; }"#;
+        let mut parser = Parser::new();
+        let borrowed = parser.parse_borrowed(input).unwrap();
+        let owned = borrowed.to_owned();
+
+        assert_eq!(owned.get("host").unwrap().as_string(), Some("localhost"));
+        assert_eq!(owned.get("enabled").unwrap().as_boolean(), Some(true));
+    }
+}
@@ -15,6 +15,10 @@ pub enum CfgppError {
         message: String,
         line: usize,
         column: usize,
+        /// Byte offset of the start of the offending span in the source
+        start: usize,
+        /// Byte offset of the end (exclusive) of the offending span
+        end: usize,
     },
 
     /// Type errors when accessing values
@@ -25,8 +29,12 @@ pub enum CfgppError {
     },
 
     /// Key not found in object
-    #[error("Key not found: {key}")]
-    KeyNotFound { key: String },
+    #[error("Key not found: {key}{}", suggestion.as_deref().map(|s| format!(" (did you mean '{}'?)", s)).unwrap_or_default())]
+    KeyNotFound {
+        key: String,
+        /// Closest sibling key by edit distance, if any was close enough
+        suggestion: Option<String>,
+    },
 
     /// Index out of bounds for arrays
     #[error("Index out of bounds: {index}")]
@@ -59,12 +67,27 @@ pub enum CfgppError {
 }
 
 impl CfgppError {
-    /// Create a new syntax error
+    /// Create a new syntax error with no known byte span (e.g. synthesized
+    /// without a `Token` in hand). Prefer `syntax_error_spanned` when a
+    /// token's range is available.
     pub fn syntax_error(message: impl Into<String>, line: usize, column: usize) -> Self {
+        Self::syntax_error_spanned(message, line, column, 0, 0)
+    }
+
+    /// Create a new syntax error with the byte span of the offending token
+    pub fn syntax_error_spanned(
+        message: impl Into<String>,
+        line: usize,
+        column: usize,
+        start: usize,
+        end: usize,
+    ) -> Self {
         Self::SyntaxError {
             message: message.into(),
             line,
             column,
+            start,
+            end,
         }
     }
 
@@ -78,7 +101,32 @@ impl CfgppError {
 
     /// Create a new key not found error
     pub fn key_not_found(key: impl Into<String>) -> Self {
-        Self::KeyNotFound { key: key.into() }
+        Self::KeyNotFound {
+            key: key.into(),
+            suggestion: None,
+        }
+    }
+
+    /// Create a new key not found error, suggesting the closest sibling key
+    /// (by Levenshtein distance) if one is close enough to plausibly be a
+    /// typo. Ties are broken by shortest candidate, then lexicographically.
+    pub fn key_not_found_with_candidates(key: impl Into<String>, candidates: &[String]) -> Self {
+        let key = key.into();
+        let threshold = (key.len() / 3).max(1);
+
+        let suggestion = candidates
+            .iter()
+            .map(|candidate| (candidate, levenshtein_distance(&key, candidate)))
+            .filter(|(_, distance)| *distance <= threshold)
+            .min_by(|(a, a_dist), (b, b_dist)| {
+                a_dist
+                    .cmp(b_dist)
+                    .then_with(|| a.len().cmp(&b.len()))
+                    .then_with(|| a.cmp(b))
+            })
+            .map(|(candidate, _)| candidate.clone());
+
+        Self::KeyNotFound { key, suggestion }
     }
 
     /// Create a new index out of bounds error
@@ -125,6 +173,101 @@ impl CfgppError {
             _ => None,
         }
     }
+
+    /// Get the byte span `(start, end)` if this is a syntax error
+    pub fn span(&self) -> Option<(usize, usize)> {
+        match self {
+            CfgppError::SyntaxError { start, end, .. } => Some((*start, *end)),
+            _ => None,
+        }
+    }
+
+    /// Render an `annotate-snippets`-style diagnostic: the offending source
+    /// line with a caret underline under the exact span, e.g.
+    ///
+    /// ```text
+    /// error: Expected Equals, found Semicolon
+    ///  --> config.cfgpp:4:21
+    ///   |
+    /// 4 |     missing_value = ;
+    ///   |                     ^
+    /// ```
+    ///
+    /// Falls back to the single-line `Display` output for variants without a
+    /// byte span (e.g. `IncludeError`, `EnvVarError`).
+    pub fn render(&self, source: &str, filename: Option<&str>) -> String {
+        let (message, line, column, start, end) = match self {
+            CfgppError::SyntaxError { message, line, column, start, end } => {
+                (message.as_str(), *line, *column, *start, *end)
+            }
+            other => return other.to_string(),
+        };
+
+        let clamped_start = start.min(source.len());
+        let line_start = source[..clamped_start]
+            .rfind('\n')
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let line_end = source[clamped_start..]
+            .find('\n')
+            .map(|i| clamped_start + i)
+            .unwrap_or(source.len());
+        let line_text = &source[line_start..line_end];
+
+        let caret_offset = clamped_start - line_start;
+        let caret_width = end.saturating_sub(start).max(1).min(line_text.len().saturating_sub(caret_offset).max(1));
+
+        let location = match filename {
+            Some(name) => format!("{}:{}:{}", name, line, column),
+            None => format!("{}:{}", line, column),
+        };
+
+        let gutter = line.to_string();
+        let pad = " ".repeat(gutter.len());
+
+        format!(
+            "error: {message}\n{pad}--> {location}\n{pad} |\n{gutter} | {line_text}\n{pad} | {caret_pad}{carets}",
+            message = message,
+            pad = pad,
+            location = location,
+            gutter = gutter,
+            line_text = line_text,
+            caret_pad = " ".repeat(caret_offset),
+            carets = "^".repeat(caret_width),
+        )
+    }
+}
+
+/// Edit distance between two strings, used to suggest a likely typo fix.
+/// Counts adjacent-character transpositions (like "prot" -> "port") as a
+/// single edit, matching the typo-correction heuristic rustc uses.
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (n, m) = (a.len(), b.len());
+
+    let mut d = vec![vec![0usize; m + 1]; n + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for j in 0..=m {
+        d[0][j] = j;
+    }
+
+    for i in 1..=n {
+        for j in 1..=m {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[n][m]
 }
 
 impl From<std::io::Error> for CfgppError {
@@ -171,3 +314,37 @@ impl CfgppError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_points_at_span() {
+        let source = "invalid {\n    missing_value = ;\n}\n";
+        // The ';' is 20 bytes into line 2 (column 21), which itself starts at byte 10.
+        let semicolon_start = source.find(" ;").unwrap() + 1;
+        let err = CfgppError::syntax_error_spanned(
+            "Expected Equals, found Semicolon",
+            2,
+            21,
+            semicolon_start,
+            semicolon_start + 1,
+        );
+
+        let rendered = err.render(source, Some("config.cfgpp"));
+
+        assert!(rendered.contains("error: Expected Equals, found Semicolon"));
+        assert!(rendered.contains("--> config.cfgpp:2:21"));
+        assert!(rendered.contains("missing_value = ;"));
+        assert!(rendered.contains('^'));
+    }
+
+    #[test]
+    fn test_render_falls_back_without_span() {
+        let err = CfgppError::include_error("missing.cfgpp", "File not found in include paths");
+        let rendered = err.render("", None);
+
+        assert_eq!(rendered, err.to_string());
+    }
+}
@@ -1,9 +1,8 @@
 //! High-performance lexer for CFG++ format
 
 use crate::error::{CfgppError, CfgppResult};
+use memchr::{memchr, memchr2};
 use std::fmt;
-use std::str::Chars;
-use std::iter::Peekable;
 
 /// Token types in CFG++ format
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -57,6 +56,12 @@ pub struct Token {
     pub line: usize,
     pub column: usize,
     pub position: usize,
+    /// Byte offset of the end of this token (exclusive) in the original input
+    pub end_position: usize,
+    /// Width/signedness suffix on a numeric literal (`"u8"`, `"i32"`,
+    /// `"f64"`, ...), if `read_number` recognized one. `None` for every
+    /// other token, and for unsuffixed numbers.
+    pub suffix: Option<String>,
 }
 
 impl Token {
@@ -66,6 +71,7 @@ impl Token {
         line: usize,
         column: usize,
         position: usize,
+        end_position: usize,
     ) -> Self {
         Self {
             token_type,
@@ -73,8 +79,15 @@ impl Token {
             line,
             column,
             position,
+            end_position,
+            suffix: None,
         }
     }
+
+    /// Byte span `(start, end)` of this token in the original input
+    pub fn span(&self) -> (usize, usize) {
+        (self.position, self.end_position)
+    }
 }
 
 impl fmt::Display for Token {
@@ -87,10 +100,13 @@ impl fmt::Display for Token {
     }
 }
 
-/// High-performance lexer with SIMD optimizations where possible
+/// High-performance lexer with SIMD-accelerated scanning (via `memchr`) for
+/// its hot loops - strings, comments, and whitespace runs. It indexes
+/// directly into `input.as_bytes()` with a byte cursor (`position`) rather
+/// than stepping a `Chars` iterator one character at a time; `line`/`column`
+/// are still tracked per-character for diagnostics.
 pub struct Lexer<'a> {
     input: &'a str,
-    chars: Peekable<Chars<'a>>,
     position: usize,
     line: usize,
     column: usize,
@@ -102,7 +118,6 @@ impl<'a> Lexer<'a> {
     pub fn new(input: &'a str) -> Self {
         Self {
             input,
-            chars: input.chars().peekable(),
             position: 0,
             line: 1,
             column: 1,
@@ -132,6 +147,7 @@ impl<'a> Lexer<'a> {
             self.line,
             self.column,
             self.position,
+            self.position,
         ));
 
         Ok(std::mem::take(&mut self.tokens))
@@ -166,7 +182,7 @@ impl<'a> Lexer<'a> {
             '*' => TokenType::Multiply,
             '/' => {
                 // Check for comments
-                if self.peek() == Some(&'/') {
+                if self.peek() == Some('/') {
                     self.advance(); // consume second '/'
                     return Ok(Some(self.read_line_comment(start_line, start_column, start_position)));
                 }
@@ -174,7 +190,7 @@ impl<'a> Lexer<'a> {
             }
             ':' => {
                 // Check for namespace operator '::'
-                if self.peek() == Some(&':') {
+                if self.peek() == Some(':') {
                     self.advance(); // consume second ':'
                     return Ok(Some(Token::new(
                         TokenType::Namespace,
@@ -182,23 +198,35 @@ impl<'a> Lexer<'a> {
                         start_line,
                         start_column,
                         start_position,
+                        self.position,
                     )));
                 }
                 TokenType::Colon
             }
 
-            // String literals
-            '"' => return Ok(Some(self.read_string(start_line, start_column, start_position)?)),
+            // String literals - "\"\"\"" opens a multi-line string, a bare
+            // '"' opens a regular one.
+            '"' => {
+                let bytes = self.input.as_bytes();
+                if bytes.get(self.position) == Some(&b'"') && bytes.get(self.position + 1) == Some(&b'"') {
+                    self.position += 2;
+                    self.column += 2;
+                    return Ok(Some(self.read_triple_quoted_string(start_line, start_column, start_position)?));
+                }
+                return Ok(Some(self.read_string(start_line, start_column, start_position)?));
+            }
 
             // Environment variables
             '$' => {
-                if self.peek() == Some(&'{') {
+                if self.peek() == Some('{') {
                     return Ok(Some(self.read_env_var(start_line, start_column, start_position)?));
                 }
-                return Err(CfgppError::syntax_error(
+                return Err(CfgppError::syntax_error_spanned(
                     "Unexpected character '$'",
                     start_line,
                     start_column,
+                    start_position,
+                    self.position,
                 ));
             }
 
@@ -210,16 +238,23 @@ impl<'a> Lexer<'a> {
                 return Ok(Some(self.read_number(ch, start_line, start_column, start_position)?));
             }
 
-            // Identifiers and keywords
+            // Identifiers and keywords - "r\"...\"" is a raw string, not an
+            // identifier starting with 'r'.
             'a'..='z' | 'A'..='Z' | '_' => {
+                if ch == 'r' && self.peek() == Some('"') {
+                    self.advance(); // consume the opening quote
+                    return Ok(Some(self.read_raw_string(start_line, start_column, start_position)?));
+                }
                 return Ok(Some(self.read_identifier(ch, start_line, start_column, start_position)?));
             }
 
             _ => {
-                return Err(CfgppError::syntax_error(
+                return Err(CfgppError::syntax_error_spanned(
                     format!("Unexpected character '{}'", ch),
                     start_line,
                     start_column,
+                    start_position,
+                    self.position,
                 ));
             }
         };
@@ -230,39 +265,263 @@ impl<'a> Lexer<'a> {
             start_line,
             start_column,
             start_position,
+            self.position,
         )))
     }
 
     fn read_string(&mut self, line: usize, column: usize, position: usize) -> CfgppResult<Token> {
         let mut value = String::new();
-        let mut escaped = false;
 
-        while let Some(&ch) = self.peek() {
-            self.advance();
+        loop {
+            let bytes = self.input.as_bytes();
+            let rest = &bytes[self.position..];
+
+            // Jump straight to the next quote or escape instead of
+            // inspecting every byte; `'"'`/`'\\'` are both ASCII so this
+            // split point can never land inside a multi-byte character.
+            let idx = match memchr2(b'"', b'\\', rest) {
+                Some(idx) => idx,
+                None => {
+                    self.position = bytes.len();
+                    return Err(CfgppError::syntax_error_spanned(
+                        "Unterminated string",
+                        line,
+                        column,
+                        position,
+                        self.position,
+                    ));
+                }
+            };
 
-            if escaped {
-                match ch {
-                    'n' => value.push('\n'),
-                    'r' => value.push('\r'),
-                    't' => value.push('\t'),
-                    '\\' => value.push('\\'),
-                    '"' => value.push('"'),
-                    _ => {
-                        value.push('\\');
-                        value.push(ch);
-                    }
+            let clean = &self.input[self.position..self.position + idx];
+            value.push_str(clean);
+            self.advance_over(clean);
+            self.position += idx;
+
+            let marker = self
+                .advance()
+                .expect("memchr2 found a byte at this position");
+
+            if marker == '"' {
+                return Ok(Token::new(TokenType::String, value, line, column, position, self.position));
+            }
+
+            // marker == '\\'
+            value.push_str(&self.read_escape(line, column, position)?);
+        }
+    }
+
+    /// Reads the character(s) following a `\` already consumed by the
+    /// caller - shared by [`Lexer::read_string`] and
+    /// [`Lexer::read_triple_quoted_string`].
+    fn read_escape(&mut self, line: usize, column: usize, position: usize) -> CfgppResult<String> {
+        match self.advance() {
+            Some('n') => Ok("\n".to_string()),
+            Some('r') => Ok("\r".to_string()),
+            Some('t') => Ok("\t".to_string()),
+            Some('\\') => Ok("\\".to_string()),
+            Some('"') => Ok("\"".to_string()),
+            Some('u') => Ok(self.read_unicode_escape(line, column, position)?.to_string()),
+            Some('x') => Ok(self.read_byte_escape(line, column, position)?.to_string()),
+            Some(other) => Ok(format!("\\{}", other)),
+            None => Err(CfgppError::syntax_error_spanned(
+                "Unterminated string",
+                line,
+                column,
+                position,
+                self.position,
+            )),
+        }
+    }
+
+    /// Reads a `{XXXX}` hex block (1-6 digits) after `\u` has already been
+    /// consumed, validating it as a Unicode scalar value.
+    fn read_unicode_escape(&mut self, line: usize, column: usize, position: usize) -> CfgppResult<char> {
+        if self.advance() != Some('{') {
+            return Err(CfgppError::syntax_error_spanned(
+                "Expected '{' after \\u",
+                line,
+                column,
+                position,
+                self.position,
+            ));
+        }
+
+        let mut hex = String::new();
+        loop {
+            match self.advance() {
+                Some('}') => break,
+                Some(ch) if ch.is_ascii_hexdigit() && hex.len() < 6 => hex.push(ch),
+                _ => {
+                    return Err(CfgppError::syntax_error_spanned(
+                        "Invalid \\u{...} escape: expected 1-6 hex digits followed by '}'",
+                        line,
+                        column,
+                        position,
+                        self.position,
+                    ));
                 }
-                escaped = false;
-            } else if ch == '\\' {
-                escaped = true;
-            } else if ch == '"' {
-                return Ok(Token::new(TokenType::String, value, line, column, position));
-            } else {
-                value.push(ch);
             }
         }
 
-        Err(CfgppError::syntax_error("Unterminated string", line, column))
+        if hex.is_empty() {
+            return Err(CfgppError::syntax_error_spanned(
+                "\\u{} escape requires at least one hex digit",
+                line,
+                column,
+                position,
+                self.position,
+            ));
+        }
+
+        let code = u32::from_str_radix(&hex, 16).expect("loop only admits hex digits");
+        char::from_u32(code).ok_or_else(|| {
+            CfgppError::syntax_error_spanned(
+                format!("\\u{{{}}} is not a valid Unicode scalar value (surrogate or out of range)", hex),
+                line,
+                column,
+                position,
+                self.position,
+            )
+        })
+    }
+
+    /// Reads exactly two hex digits after `\x` has already been consumed.
+    fn read_byte_escape(&mut self, line: usize, column: usize, position: usize) -> CfgppResult<char> {
+        let mut hex = String::with_capacity(2);
+        for _ in 0..2 {
+            match self.advance() {
+                Some(ch) if ch.is_ascii_hexdigit() => hex.push(ch),
+                _ => {
+                    return Err(CfgppError::syntax_error_spanned(
+                        "\\x escape requires exactly two hex digits",
+                        line,
+                        column,
+                        position,
+                        self.position,
+                    ));
+                }
+            }
+        }
+
+        let byte = u8::from_str_radix(&hex, 16).expect("loop only admits hex digits");
+        Ok(byte as char)
+    }
+
+    /// Reads a raw string (`r"..."`, opening quote already consumed): no
+    /// escape processing at all, only the closing `"` ends the token.
+    fn read_raw_string(&mut self, line: usize, column: usize, position: usize) -> CfgppResult<Token> {
+        let bytes = self.input.as_bytes();
+        let rest = &bytes[self.position..];
+
+        let idx = match memchr(b'"', rest) {
+            Some(idx) => idx,
+            None => {
+                self.position = bytes.len();
+                return Err(CfgppError::syntax_error_spanned(
+                    "Unterminated raw string",
+                    line,
+                    column,
+                    position,
+                    self.position,
+                ));
+            }
+        };
+
+        let text = &self.input[self.position..self.position + idx];
+        let value = text.to_string();
+        self.advance_over(text);
+        self.position += idx;
+        self.advance(); // consume closing quote
+
+        Ok(Token::new(TokenType::String, value, line, column, position, self.position))
+    }
+
+    /// Reads a triple-quoted string (`"""..."""`, opening quotes already
+    /// consumed): raw newlines are allowed and only a run of three or more
+    /// `"` ends the token, so the body can contain unescaped single quotes.
+    /// Escapes (including `\u{...}` and `\xNN`) are still processed.
+    fn read_triple_quoted_string(&mut self, line: usize, column: usize, position: usize) -> CfgppResult<Token> {
+        let mut raw = String::new();
+
+        loop {
+            let bytes = self.input.as_bytes();
+            let rest = &bytes[self.position..];
+
+            let idx = match memchr2(b'"', b'\\', rest) {
+                Some(idx) => idx,
+                None => {
+                    self.position = bytes.len();
+                    return Err(CfgppError::syntax_error_spanned(
+                        "Unterminated triple-quoted string",
+                        line,
+                        column,
+                        position,
+                        self.position,
+                    ));
+                }
+            };
+
+            let clean = &self.input[self.position..self.position + idx];
+            raw.push_str(clean);
+            self.advance_over(clean);
+            self.position += idx;
+
+            if bytes[self.position] == b'"' {
+                let run = bytes[self.position..].iter().take_while(|&&b| b == b'"').count();
+                if run >= 3 {
+                    self.position += 3;
+                    self.column += 3;
+                    break;
+                }
+                // Fewer than three quotes in a row: literal content.
+                for _ in 0..run {
+                    raw.push('"');
+                }
+                self.position += run;
+                self.column += run;
+                continue;
+            }
+
+            // bytes[self.position] == '\\'
+            self.advance(); // consume the backslash
+            raw.push_str(&self.read_escape(line, column, position)?);
+        }
+
+        let value = Self::dedent_triple_quoted(&raw);
+        Ok(Token::new(TokenType::String, value, line, column, position, self.position))
+    }
+
+    /// Strips a shared leading-whitespace margin from a triple-quoted
+    /// string's lines, and drops a single leading newline right after the
+    /// opening `"""` so indented blocks can start on their own line without
+    /// a leading blank one.
+    fn dedent_triple_quoted(raw: &str) -> String {
+        let body = raw
+            .strip_prefix("\r\n")
+            .or_else(|| raw.strip_prefix('\n'))
+            .unwrap_or(raw);
+
+        let lines: Vec<&str> = body.split('\n').collect();
+        let margin = lines
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+            .min()
+            .unwrap_or(0);
+
+        if margin == 0 {
+            return body.to_string();
+        }
+
+        lines
+            .iter()
+            .map(|line| {
+                let leading = line.len() - line.trim_start_matches([' ', '\t']).len();
+                &line[leading.min(margin)..]
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
     fn read_env_var(&mut self, line: usize, column: usize, position: usize) -> CfgppResult<Token> {
@@ -270,7 +529,7 @@ impl<'a> Lexer<'a> {
         let mut value = String::from("${");
         let mut brace_count = 1;
 
-        while let Some(&ch) = self.peek() {
+        while let Some(ch) = self.peek() {
             self.advance();
             value.push(ch);
 
@@ -279,20 +538,26 @@ impl<'a> Lexer<'a> {
                 '}' => {
                     brace_count -= 1;
                     if brace_count == 0 {
-                        return Ok(Token::new(TokenType::EnvVar, value, line, column, position));
+                        return Ok(Token::new(TokenType::EnvVar, value, line, column, position, self.position));
                     }
                 }
                 _ => {}
             }
         }
 
-        Err(CfgppError::syntax_error("Unterminated environment variable", line, column))
+        Err(CfgppError::syntax_error_spanned(
+            "Unterminated environment variable",
+            line,
+            column,
+            position,
+            self.position,
+        ))
     }
 
     fn read_directive(&mut self, line: usize, column: usize, position: usize) -> CfgppResult<Token> {
         let mut value = String::from("@");
 
-        while let Some(&ch) = self.peek() {
+        while let Some(ch) = self.peek() {
             if ch.is_alphanumeric() || ch == '_' {
                 self.advance();
                 value.push(ch);
@@ -304,10 +569,16 @@ impl<'a> Lexer<'a> {
         let token_type = match value.as_str() {
             "@include" => TokenType::Include,
             "@import" => TokenType::Import,
-            _ => return Err(CfgppError::syntax_error(format!("Unknown directive '{}'", value), line, column)),
+            _ => return Err(CfgppError::syntax_error_spanned(
+                format!("Unknown directive '{}'", value),
+                line,
+                column,
+                position,
+                self.position,
+            )),
         };
 
-        Ok(Token::new(token_type, value, line, column, position))
+        Ok(Token::new(token_type, value, line, column, position, self.position))
     }
 
     fn read_number(&mut self, first: char, line: usize, column: usize, position: usize) -> CfgppResult<Token> {
@@ -315,7 +586,7 @@ impl<'a> Lexer<'a> {
         value.push(first);
         let mut is_float = false;
 
-        while let Some(&ch) = self.peek() {
+        while let Some(ch) = self.peek() {
             match ch {
                 '0'..='9' => {
                     self.advance();
@@ -335,7 +606,7 @@ impl<'a> Lexer<'a> {
                     value.push(ch);
                     
                     // Handle optional sign
-                    if let Some(&sign_ch) = self.peek() {
+                    if let Some(sign_ch) = self.peek() {
                         if sign_ch == '+' || sign_ch == '-' {
                             self.advance();
                             value.push(sign_ch);
@@ -346,20 +617,55 @@ impl<'a> Lexer<'a> {
             }
         }
 
-        let token_type = if is_float {
-            TokenType::Double
-        } else {
-            TokenType::Integer
+        let suffix = self.read_numeric_suffix();
+
+        let token_type = match &suffix {
+            // A float suffix (`5f32`) makes the literal a Double even
+            // without a decimal point or exponent.
+            Some(s) if s.starts_with('f') => TokenType::Double,
+            _ if is_float => TokenType::Double,
+            _ => TokenType::Integer,
         };
 
-        Ok(Token::new(token_type, value, line, column, position))
+        let mut token = Token::new(token_type, value, line, column, position, self.position);
+        token.suffix = suffix;
+        Ok(token)
+    }
+
+    /// Numeric literal suffixes this lexer recognizes: `i8`/`i16`/`i32`/`i64`,
+    /// `u8`/`u16`/`u32`/`u64`, `f32`/`f64`.
+    const NUMERIC_SUFFIXES: &'static [&'static str] =
+        &["i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "f32", "f64"];
+
+    /// If the identifier-like run right after a numeric literal is exactly
+    /// one of [`Lexer::NUMERIC_SUFFIXES`], consume it and return it.
+    /// Otherwise consumes nothing, leaving the run to be read as its own
+    /// token the way it always was (e.g. `5 feet` keeps tokenizing as
+    /// `Integer("5")` followed by `Identifier("feet")`).
+    fn read_numeric_suffix(&mut self) -> Option<String> {
+        let rest = &self.input.as_bytes()[self.position..];
+        let mut len = 0;
+        while len < rest.len() && rest[len].is_ascii_alphanumeric() {
+            len += 1;
+        }
+        let run = std::str::from_utf8(&rest[..len])
+            .expect("a run of ASCII alphanumeric bytes is always valid UTF-8");
+
+        if !Self::NUMERIC_SUFFIXES.contains(&run) {
+            return None;
+        }
+
+        let run = run.to_string();
+        self.position += len;
+        self.column += len; // suffix bytes are all single-byte ASCII
+        Some(run)
     }
 
     fn read_identifier(&mut self, first: char, line: usize, column: usize, position: usize) -> CfgppResult<Token> {
         let mut value = String::new();
         value.push(first);
 
-        while let Some(&ch) = self.peek() {
+        while let Some(ch) = self.peek() {
             if ch.is_alphanumeric() || ch == '_' {
                 self.advance();
                 value.push(ch);
@@ -375,50 +681,90 @@ impl<'a> Lexer<'a> {
             _ => TokenType::Identifier,
         };
 
-        Ok(Token::new(token_type, value, line, column, position))
+        Ok(Token::new(token_type, value, line, column, position, self.position))
     }
 
     fn read_line_comment(&mut self, line: usize, column: usize, position: usize) -> Token {
-        let mut value = String::from("//");
+        // One scan to end-of-line instead of a push per character.
+        let rest = &self.input.as_bytes()[self.position..];
+        let len = memchr(b'\n', rest).unwrap_or(rest.len());
+        let text = &self.input[self.position..self.position + len];
 
-        while let Some(&ch) = self.peek() {
-            if ch == '\n' {
-                break;
-            }
-            self.advance();
-            value.push(ch);
-        }
+        self.position += len;
+        self.advance_over(text);
 
-        Token::new(TokenType::Comment, value, line, column, position)
+        Token::new(
+            TokenType::Comment,
+            format!("//{}", text),
+            line,
+            column,
+            position,
+            self.position,
+        )
     }
 
     fn skip_whitespace(&mut self) {
-        while let Some(&ch) = self.peek() {
-            if ch.is_whitespace() {
-                self.advance();
-            } else {
-                break;
+        let bytes = self.input.as_bytes();
+        while self.position < bytes.len() {
+            match bytes[self.position] {
+                b'\n' => {
+                    self.position += 1;
+                    self.line += 1;
+                    self.column = 1;
+                }
+                // ASCII whitespace other than '\n', scanned byte-by-byte
+                // without going through `char` decoding.
+                b' ' | b'\t' | b'\r' | 0x0B | 0x0C => {
+                    self.position += 1;
+                    self.column += 1;
+                }
+                b if b.is_ascii() => break,
+                // Non-ASCII byte: fall back to decoding a full `char` to
+                // check Unicode whitespace (e.g. U+00A0 NBSP).
+                _ => match self.peek() {
+                    Some(ch) if ch.is_whitespace() => {
+                        self.position += ch.len_utf8();
+                        self.column += 1;
+                    }
+                    _ => break,
+                },
             }
         }
     }
 
-    fn peek(&mut self) -> Option<&char> {
-        self.chars.peek()
-    }
-
-    fn advance(&mut self) -> Option<char> {
-        if let Some(ch) = self.chars.next() {
-            self.position += 1;
+    /// Advance `line`/`column` bookkeeping over a slice already known to be
+    /// part of the input (the caller moves `self.position` over its bytes
+    /// itself; this leaves `self.position` alone) - used after bulk-copying a run found
+    /// via `memchr`/`memchr2` instead of stepping through it one `advance()`
+    /// call at a time.
+    fn advance_over(&mut self, text: &str) {
+        for ch in text.chars() {
             if ch == '\n' {
                 self.line += 1;
                 self.column = 1;
             } else {
                 self.column += 1;
             }
-            Some(ch)
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        // Byte offset, not char count, so spans can be used to slice the
+        // original `&str` directly - required once input contains any
+        // multi-byte UTF-8 characters.
+        self.position += ch.len_utf8();
+        if ch == '\n' {
+            self.line += 1;
+            self.column = 1;
         } else {
-            None
+            self.column += 1;
         }
+        Some(ch)
     }
 }
 
@@ -453,6 +799,191 @@ mod tests {
         assert_eq!(tokens[0].value, "hello world");
     }
 
+    #[test]
+    fn test_unicode_escape_parses_scalar_value() {
+        let input = r#""caf\u{e9}""#;
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens[0].value, "café");
+    }
+
+    #[test]
+    fn test_unicode_escape_rejects_surrogate() {
+        let input = r#""\u{d800}""#;
+        let mut lexer = Lexer::new(input);
+        assert!(lexer.tokenize().is_err());
+    }
+
+    #[test]
+    fn test_unicode_escape_rejects_too_many_hex_digits() {
+        let input = r#""\u{1234567}""#;
+        let mut lexer = Lexer::new(input);
+        assert!(lexer.tokenize().is_err());
+    }
+
+    #[test]
+    fn test_byte_escape_two_hex_digits() {
+        let input = r#""\x41\x42""#;
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens[0].value, "AB");
+    }
+
+    #[test]
+    fn test_byte_escape_requires_exactly_two_hex_digits() {
+        let input = r#""\x4""#;
+        let mut lexer = Lexer::new(input);
+        assert!(lexer.tokenize().is_err());
+    }
+
+    #[test]
+    fn test_raw_string_keeps_backslashes_literal() {
+        let input = r#"r"C:\no\escapes\here""#;
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens[0].token_type, TokenType::String);
+        assert_eq!(tokens[0].value, r"C:\no\escapes\here");
+    }
+
+    #[test]
+    fn test_raw_string_prefix_does_not_break_identifiers() {
+        let input = "root = 1;";
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens[0].token_type, TokenType::Identifier);
+        assert_eq!(tokens[0].value, "root");
+    }
+
+    #[test]
+    fn test_triple_quoted_string_allows_raw_newlines_and_unescaped_quotes() {
+        let input = "\"\"\"line one\nhas a \"quote\" in it\nline three\"\"\"";
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens[0].token_type, TokenType::String);
+        assert_eq!(tokens[0].value, "line one\nhas a \"quote\" in it\nline three");
+    }
+
+    #[test]
+    fn test_triple_quoted_string_strips_common_indentation() {
+        let input = "\"\"\"\n    first\n    second\n    \"\"\"";
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens[0].value, "first\nsecond\n");
+    }
+
+    #[test]
+    fn test_token_byte_span() {
+        let input = "port = 5432;";
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        // "port" spans bytes [0, 4)
+        assert_eq!(tokens[0].span(), (0, 4));
+        // "5432" spans bytes [7, 11)
+        assert_eq!(tokens[2].span(), (7, 11));
+    }
+
+    #[test]
+    fn test_byte_span_tracks_utf8_multibyte_width() {
+        // 'é' is 2 bytes in UTF-8, so the identifier is 5 chars but 6 bytes.
+        let input = r#"héllo = "wörld";"#;
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens[0].span(), (0, 6));
+        assert_eq!(&input[0..6], "héllo");
+
+        // The string token starts right after "héllo = " (6 + 3 = 9 bytes in).
+        assert_eq!(tokens[2].token_type, TokenType::String);
+        assert_eq!(&input[tokens[2].span().0..tokens[2].span().1], "\"wörld\"");
+    }
+
+    #[test]
+    fn test_numeric_suffix_recognized_and_consumed() {
+        let input = "5432u16 255u8 3.14f32 5f64 7";
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens[0].value, "5432");
+        assert_eq!(tokens[0].token_type, TokenType::Integer);
+        assert_eq!(tokens[0].suffix.as_deref(), Some("u16"));
+
+        assert_eq!(tokens[1].value, "255");
+        assert_eq!(tokens[1].suffix.as_deref(), Some("u8"));
+
+        assert_eq!(tokens[2].value, "3.14");
+        assert_eq!(tokens[2].token_type, TokenType::Double);
+        assert_eq!(tokens[2].suffix.as_deref(), Some("f32"));
+
+        // No decimal point, but "f64" forces a Double token.
+        assert_eq!(tokens[3].value, "5");
+        assert_eq!(tokens[3].token_type, TokenType::Double);
+        assert_eq!(tokens[3].suffix.as_deref(), Some("f64"));
+
+        // Unsuffixed numbers are unaffected.
+        assert_eq!(tokens[4].value, "7");
+        assert_eq!(tokens[4].suffix, None);
+    }
+
+    #[test]
+    fn test_non_suffix_identifier_after_number_is_untouched() {
+        // "feet" isn't a recognized suffix, so it stays a separate token -
+        // matching the pre-existing behavior for any trailing identifier.
+        let input = "5 feet";
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens[0].value, "5");
+        assert_eq!(tokens[0].suffix, None);
+        assert_eq!(tokens[1].token_type, TokenType::Identifier);
+        assert_eq!(tokens[1].value, "feet");
+    }
+
+    #[test]
+    fn test_string_with_multibyte_text_either_side_of_escape() {
+        // Exercises the memchr2 split in `read_string` landing around
+        // multi-byte chars both before and after an escape sequence.
+        let input = "name = \"caf\\né r\\tésumé\";";
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens[2].token_type, TokenType::String);
+        assert_eq!(tokens[2].value, "caf\né r\tésumé");
+    }
+
+    #[test]
+    fn test_multibyte_parity_across_whitespace_strings_and_comments() {
+        // A battery of multi-byte UTF-8 inputs that previously went through
+        // a char-by-char `Peekable<Chars>` walk and now go through the
+        // byte-cursor + memchr paths; spans must still land on exact char
+        // boundaries and slice back to the expected text.
+        let cases = [
+            "héllo = \"wörld\";",
+            "  \u{00A0} café = \"日本語\"; // 注释\n",
+            "emoji = \"🎉🎊\"; // party 🎉\n",
+            "naïve\tname = \"Zürich\";\r\n",
+        ];
+
+        for input in cases {
+            let mut lexer = Lexer::new(input);
+            let tokens = lexer.tokenize().unwrap();
+
+            for token in &tokens {
+                let (start, end) = token.span();
+                assert!(input.is_char_boundary(start), "bad start boundary in {:?}", input);
+                assert!(input.is_char_boundary(end), "bad end boundary in {:?}", input);
+            }
+
+            assert_eq!(tokens.last().unwrap().token_type, TokenType::Eof);
+        }
+    }
+
     #[test]
     fn test_number_parsing() {
         let input = "123 45.67 1.23e-4";
@@ -37,16 +37,21 @@
 //! ```
 
 pub mod ast;
+pub mod borrowed;
 pub mod error;
 pub mod lexer;
 pub mod parser;
+pub mod query;
 pub mod schema;
+pub mod types;
 pub mod value;
+pub mod visitor;
 
 #[cfg(feature = "serde")]
 pub mod serde_support;
 
 pub use ast::*;
+pub use borrowed::BorrowedValue;
 pub use error::*;
 pub use parser::*;
 pub use value::*;
@@ -56,7 +61,13 @@ pub mod prelude {
     pub use crate::{CfgppValue, Parser, CfgppError, CfgppResult};
     
     #[cfg(feature = "schema-validation")]
-    pub use crate::schema::{Schema, ValidationError};
+    pub use crate::schema::{
+        CompatibilityChange, CompatibilityKind, CompatibilityReport, ErrorCode, JsonPath, Schema, Severity,
+        ValidationDiagnostic,
+    };
+
+    #[cfg(feature = "schema-validation")]
+    pub use crate::types::{CfgppType, TypeError};
 }
 
 /// Current version of the CFG++ parser
@@ -3,10 +3,11 @@
 use crate::{
     error::{CfgppError, CfgppResult},
     lexer::{Lexer, Token, TokenType},
-    value::CfgppValue,
+    value::{CfgppObject, CfgppValue},
 };
 use std::collections::HashMap;
 use std::path::Path;
+use std::rc::Rc;
 
 /// Parser configuration options
 #[derive(Debug, Clone)]
@@ -21,6 +22,20 @@ pub struct ParserOptions {
     pub include_paths: Vec<String>,
     /// Validate syntax only (don't build value tree)
     pub syntax_only: bool,
+    /// Collect multiple syntax errors instead of bailing out on the first one.
+    /// When enabled, `parse` synchronizes to the next statement boundary on
+    /// error and keeps going; use `Parser::take_errors` to drain diagnostics.
+    pub error_recovery: bool,
+    /// Intern object keys and enum-value identifiers so a key that repeats
+    /// across many objects (e.g. `host`, `port` in hundreds of blocks)
+    /// shares one pooled allocation instead of a fresh one per occurrence.
+    pub intern_keys: bool,
+    /// Skip recursing into object/array blocks and instead capture each
+    /// block's exact source text as a `CfgppValue::Raw`, deferring parsing
+    /// until [`CfgppValue::force`] (or [`CfgppValue::get_path_owned`]) is
+    /// called on it. Useful for large or opaque sub-configs that aren't
+    /// always read.
+    pub lazy: bool,
 }
 
 impl Default for ParserOptions {
@@ -31,6 +46,9 @@ impl Default for ParserOptions {
             max_include_depth: 10,
             include_paths: vec![".".to_string()],
             syntax_only: false,
+            error_recovery: false,
+            intern_keys: true,
+            lazy: false,
         }
     }
 }
@@ -41,6 +59,14 @@ pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
     include_depth: usize,
+    /// Errors accumulated while `options.error_recovery` is enabled
+    errors: Vec<CfgppError>,
+    /// Pool of interned key/identifier strings, keyed by their contents, so
+    /// repeated keys share one `Rc<str>` instead of reallocating per use
+    key_pool: HashMap<Box<str>, Rc<str>>,
+    /// Full source of the input currently being parsed, kept around so
+    /// `options.lazy` can slice out a block's exact source text by byte span
+    source: String,
 }
 
 impl Parser {
@@ -56,18 +82,234 @@ impl Parser {
             tokens: Vec::new(),
             current: 0,
             include_depth: 0,
+            errors: Vec::new(),
+            key_pool: HashMap::new(),
+            source: String::new(),
         }
     }
 
+    /// Intern `s`, returning the pooled `Rc<str>` if this exact string has
+    /// been seen before, or pooling and returning a fresh one otherwise.
+    fn intern(&mut self, s: &str) -> Rc<str> {
+        if let Some(existing) = self.key_pool.get(s) {
+            return existing.clone();
+        }
+
+        let interned: Rc<str> = Rc::from(s);
+        self.key_pool.insert(Box::from(s), interned.clone());
+        interned
+    }
+
+    /// Number of distinct strings currently held in the key pool. Stays
+    /// proportional to the number of *distinct* keys parsed so far, not the
+    /// total number of key occurrences.
+    pub fn interned_key_count(&self) -> usize {
+        self.key_pool.len()
+    }
+
     /// Parse a CFG++ string into a value
+    ///
+    /// When `options.error_recovery` is enabled, this returns `Ok` with a
+    /// best-effort tree as long as at least one node parsed; drain the
+    /// accumulated diagnostics with `take_errors`.
     pub fn parse(&mut self, input: &str) -> CfgppResult<CfgppValue> {
         // Tokenize input
         let mut lexer = Lexer::new(input);
         self.tokens = lexer.tokenize()?;
         self.current = 0;
+        self.errors.clear();
+        self.source = input.to_string();
 
         // Parse the token stream
-        self.parse_value()
+        let result = self.parse_value();
+
+        if self.options.error_recovery && !self.errors.is_empty() {
+            match result {
+                Ok(value) => Ok(value),
+                Err(e) => {
+                    self.errors.push(e);
+                    Ok(CfgppValue::null())
+                }
+            }
+        } else {
+            result
+        }
+    }
+
+    /// Drain the errors accumulated during the last `parse` call. Only
+    /// populated when `options.error_recovery` is enabled.
+    pub fn take_errors(&mut self) -> Vec<CfgppError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// Parse `input` into a [`BorrowedValue`] that borrows its strings and
+    /// object keys directly from `input` instead of allocating, falling back
+    /// to an owned `Cow::Owned` only where a quoted string contains an escape
+    /// sequence or is triple-quoted. Covers the same object/array/scalar grammar as `parse`, but
+    /// does not support include directives or environment variable expansion
+    /// - use the owned `parse` for configs that need those.
+    pub fn parse_borrowed<'a>(&mut self, input: &'a str) -> CfgppResult<crate::borrowed::BorrowedValue<'a>> {
+        let mut lexer = Lexer::new(input);
+        self.tokens = lexer.tokenize()?;
+        self.current = 0;
+        self.errors.clear();
+        self.source = input.to_string();
+
+        self.parse_borrowed_value(input)
+    }
+
+    /// Borrow the exact source slice a string/identifier token spans,
+    /// without its surrounding quotes for `String`/`Enum` tokens (accounting
+    /// for the `r"` raw-string prefix), or clone `token.value` if the lexer
+    /// had to transform the content - detected either by an escape in the
+    /// slice (a backslash - bare identifiers never have one) or, for
+    /// triple-quoted strings, unconditionally, since dedenting can change
+    /// the content even when no escape was used. Raw strings always borrow,
+    /// since their backslashes are literal text.
+    fn borrow_token_text<'a>(input: &'a str, token: &Token) -> std::borrow::Cow<'a, str> {
+        if token.token_type == TokenType::String {
+            let prefix = &input[token.position..token.end_position.min(input.len())];
+            if prefix.starts_with("\"\"\"") {
+                return std::borrow::Cow::Owned(token.value.clone());
+            }
+
+            let is_raw = prefix.starts_with("r\"");
+            let inner_start = if is_raw {
+                token.position + 2
+            } else {
+                token.position + 1
+            };
+            let inner_end = token.end_position - 1;
+
+            return match input.get(inner_start..inner_end) {
+                Some(slice) if is_raw || !slice.contains('\\') => std::borrow::Cow::Borrowed(slice),
+                _ => std::borrow::Cow::Owned(token.value.clone()),
+            };
+        }
+
+        match input.get(token.position..token.end_position) {
+            Some(slice) if !slice.contains('\\') => std::borrow::Cow::Borrowed(slice),
+            _ => std::borrow::Cow::Owned(token.value.clone()),
+        }
+    }
+
+    fn parse_borrowed_value<'a>(&mut self, input: &'a str) -> CfgppResult<crate::borrowed::BorrowedValue<'a>> {
+        use crate::borrowed::BorrowedValue;
+
+        match self.current_token()?.token_type {
+            TokenType::String => {
+                let token = self.advance()?;
+                Ok(BorrowedValue::String(Self::borrow_token_text(input, token)))
+            }
+            TokenType::Integer => {
+                let value = self.parse_integer()?;
+                Ok(match value {
+                    CfgppValue::Integer(i) => BorrowedValue::Integer(i),
+                    CfgppValue::UInteger(u) => BorrowedValue::UInteger(u),
+                    other => return Err(CfgppError::parse_error(
+                        format!("Unsupported integer literal in borrowed mode: {}", other.type_name())
+                    )),
+                })
+            }
+            TokenType::Double => {
+                let token = self.advance()?;
+                let value = token.value.parse::<f64>()
+                    .map_err(|_| CfgppError::syntax_error_spanned(
+                        format!("Invalid double: {}", token.value),
+                        token.line,
+                        token.column,
+                        token.position,
+                        token.end_position,
+                    ))?;
+                Ok(BorrowedValue::Double(value))
+            }
+            TokenType::Boolean => {
+                let token = self.advance()?;
+                let value = match token.value.as_str() {
+                    "true" => true,
+                    "false" => false,
+                    _ => return Err(CfgppError::syntax_error_spanned(
+                        format!("Invalid boolean: {}", token.value),
+                        token.line,
+                        token.column,
+                        token.position,
+                        token.end_position,
+                    )),
+                };
+                Ok(BorrowedValue::Boolean(value))
+            }
+            TokenType::Null => {
+                self.advance()?;
+                Ok(BorrowedValue::Null)
+            }
+            TokenType::LeftBrace => self.parse_borrowed_object(input),
+            TokenType::LeftBracket => self.parse_borrowed_array(input),
+            TokenType::Identifier => self.parse_borrowed_identifier_or_object(input),
+            _ => {
+                let token = self.current_token()?;
+                Err(CfgppError::syntax_error_spanned(
+                    format!("Unexpected token: {}", token.value),
+                    token.line,
+                    token.column,
+                    token.position,
+                    token.end_position,
+                ))
+            }
+        }
+    }
+
+    fn parse_borrowed_object<'a>(&mut self, input: &'a str) -> CfgppResult<crate::borrowed::BorrowedValue<'a>> {
+        use crate::borrowed::BorrowedValue;
+
+        self.expect(TokenType::LeftBrace)?;
+        let mut fields = Vec::new();
+
+        while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+            let key_token = self.expect_identifier()?;
+            let key = Self::borrow_token_text(input, key_token);
+
+            self.expect(TokenType::Equals)?;
+            let value = self.parse_borrowed_value(input)?;
+            fields.push((key, value));
+
+            if self.check(TokenType::Semicolon) {
+                self.advance()?;
+            }
+        }
+
+        self.expect(TokenType::RightBrace)?;
+        Ok(BorrowedValue::Object(fields))
+    }
+
+    fn parse_borrowed_array<'a>(&mut self, input: &'a str) -> CfgppResult<crate::borrowed::BorrowedValue<'a>> {
+        use crate::borrowed::BorrowedValue;
+
+        self.expect(TokenType::LeftBracket)?;
+        let mut items = Vec::new();
+
+        while !self.check(TokenType::RightBracket) && !self.is_at_end() {
+            items.push(self.parse_borrowed_value(input)?);
+
+            if self.check(TokenType::Comma) {
+                self.advance()?;
+            }
+        }
+
+        self.expect(TokenType::RightBracket)?;
+        Ok(BorrowedValue::Array(items))
+    }
+
+    fn parse_borrowed_identifier_or_object<'a>(&mut self, input: &'a str) -> CfgppResult<crate::borrowed::BorrowedValue<'a>> {
+        use crate::borrowed::BorrowedValue;
+
+        let identifier_token = self.advance()?;
+        let identifier_text = Self::borrow_token_text(input, identifier_token);
+
+        if self.check(TokenType::LeftBrace) {
+            self.parse_borrowed_object(input)
+        } else {
+            Ok(BorrowedValue::Enum(identifier_text))
+        }
     }
 
     /// Parse a CFG++ file into a value
@@ -117,11 +359,16 @@ impl Parser {
             TokenType::Identifier => self.parse_identifier_or_object(),
             TokenType::Include | TokenType::Import => self.parse_include(),
             TokenType::EnvVar => self.parse_env_var(),
-            _ => Err(CfgppError::syntax_error(
-                format!("Unexpected token: {}", self.current_token()?.value),
-                self.current_token()?.line,
-                self.current_token()?.column,
-            )),
+            _ => {
+                let token = self.current_token()?;
+                Err(CfgppError::syntax_error_spanned(
+                    format!("Unexpected token: {}", token.value),
+                    token.line,
+                    token.column,
+                    token.position,
+                    token.end_position,
+                ))
+            }
         }
     }
 
@@ -132,23 +379,123 @@ impl Parser {
 
     fn parse_integer(&mut self) -> CfgppResult<CfgppValue> {
         let token = self.advance()?;
-        let value = token.value.parse::<i64>()
-            .map_err(|_| CfgppError::syntax_error(
-                format!("Invalid integer: {}", token.value),
-                token.line,
-                token.column,
-            ))?;
-        Ok(CfgppValue::integer(value))
+
+        if let Some(suffix) = &token.suffix {
+            return Self::parse_sized_integer(
+                &token.value, suffix, token.line, token.column, token.position, token.end_position,
+            );
+        }
+
+        if let Ok(value) = token.value.parse::<i64>() {
+            return Ok(CfgppValue::integer(value));
+        }
+        if let Ok(value) = token.value.parse::<u64>() {
+            return Ok(CfgppValue::uinteger(value));
+        }
+        #[cfg(feature = "arbitrary_precision")]
+        {
+            return Ok(CfgppValue::big_number(token.value.clone()));
+        }
+        #[allow(unreachable_code)]
+        Err(CfgppError::syntax_error_spanned(
+            format!("Invalid integer: {}", token.value),
+            token.line,
+            token.column,
+            token.position,
+            token.end_position,
+        ))
+    }
+
+    /// Parse a suffixed integer literal (`255u8`, `127i8`, `18446744073709551615u64`, ...),
+    /// checking that its digits fit within the declared bit width. The lexer
+    /// emits `-` as its own token, so a negative literal such as `-1i32`
+    /// never reaches this function.
+    fn parse_sized_integer(
+        digits: &str,
+        suffix: &str,
+        line: usize,
+        column: usize,
+        position: usize,
+        end_position: usize,
+    ) -> CfgppResult<CfgppValue> {
+        let bits: u8 = suffix[1..]
+            .parse()
+            .expect("lexer only emits suffixes with a numeric width");
+        let signed = suffix.starts_with('i');
+
+        let out_of_range = || {
+            CfgppError::syntax_error_spanned(
+                format!("{} is out of range for {}", digits, suffix),
+                line,
+                column,
+                position,
+                end_position,
+            )
+        };
+        let invalid = || {
+            CfgppError::syntax_error_spanned(
+                format!("Invalid {} literal: {}", suffix, digits),
+                line,
+                column,
+                position,
+                end_position,
+            )
+        };
+
+        if signed {
+            let value: i64 = digits.parse().map_err(|_| invalid())?;
+            let (min, max): (i64, i64) = match bits {
+                8 => (i8::MIN as i64, i8::MAX as i64),
+                16 => (i16::MIN as i64, i16::MAX as i64),
+                32 => (i32::MIN as i64, i32::MAX as i64),
+                64 => (i64::MIN, i64::MAX),
+                _ => unreachable!("lexer only emits i8/i16/i32/i64 suffixes"),
+            };
+            if value < min || value > max {
+                return Err(out_of_range());
+            }
+            Ok(CfgppValue::sized_integer(value, bits, true))
+        } else {
+            let value: u64 = digits.parse().map_err(|_| invalid())?;
+            let max: u64 = match bits {
+                8 => u8::MAX as u64,
+                16 => u16::MAX as u64,
+                32 => u32::MAX as u64,
+                64 => u64::MAX,
+                _ => unreachable!("lexer only emits u8/u16/u32/u64 suffixes"),
+            };
+            if value > max {
+                return Err(out_of_range());
+            }
+            Ok(CfgppValue::sized_integer(value as i64, bits, false))
+        }
     }
 
     fn parse_double(&mut self) -> CfgppResult<CfgppValue> {
         let token = self.advance()?;
         let value = token.value.parse::<f64>()
-            .map_err(|_| CfgppError::syntax_error(
+            .map_err(|_| CfgppError::syntax_error_spanned(
                 format!("Invalid double: {}", token.value),
                 token.line,
                 token.column,
+                token.position,
+                token.end_position,
             ))?;
+
+        if token.suffix.as_deref() == Some("f32") {
+            let narrowed = value as f32;
+            if narrowed.is_infinite() && !value.is_infinite() {
+                return Err(CfgppError::syntax_error_spanned(
+                    format!("{} is out of range for f32", token.value),
+                    token.line,
+                    token.column,
+                    token.position,
+                    token.end_position,
+                ));
+            }
+            return Ok(CfgppValue::double(narrowed as f64));
+        }
+
         Ok(CfgppValue::double(value))
     }
 
@@ -157,10 +504,12 @@ impl Parser {
         let value = match token.value.as_str() {
             "true" => true,
             "false" => false,
-            _ => return Err(CfgppError::syntax_error(
+            _ => return Err(CfgppError::syntax_error_spanned(
                 format!("Invalid boolean: {}", token.value),
                 token.line,
                 token.column,
+                token.position,
+                token.end_position,
             )),
         };
         Ok(CfgppValue::boolean(value))
@@ -172,40 +521,164 @@ impl Parser {
     }
 
     fn parse_object(&mut self) -> CfgppResult<CfgppValue> {
+        if self.options.lazy {
+            return self.parse_raw_block(TokenType::LeftBrace, TokenType::RightBrace);
+        }
+
+        let object = self.parse_object_body()?;
+
+        if self.options.syntax_only {
+            Ok(CfgppValue::null())
+        } else {
+            Ok(CfgppValue::object_with_values(object))
+        }
+    }
+
+    /// Capture the source span of a balanced `open`/`close` delimited block
+    /// (starting at the current token, which must be `open`) without parsing
+    /// its contents, returning it as a `CfgppValue::Raw` of the exact source
+    /// text. Used by `options.lazy` to defer parsing of object/array blocks.
+    fn parse_raw_block(&mut self, open: TokenType, close: TokenType) -> CfgppResult<CfgppValue> {
+        let start = self.expect(open)?.position;
+        let mut depth = 1;
+
+        loop {
+            let token = self.current_token()?;
+            if token.token_type == TokenType::Eof {
+                return Err(CfgppError::syntax_error_spanned(
+                    "Unterminated block while skipping for lazy parsing",
+                    token.line,
+                    token.column,
+                    token.position,
+                    token.end_position,
+                ));
+            }
+
+            if token.token_type == open {
+                depth += 1;
+                self.advance()?;
+            } else if token.token_type == close {
+                depth -= 1;
+                let end = self.advance()?.end_position;
+                if depth == 0 {
+                    return Ok(CfgppValue::Raw(self.source[start..end].to_string()));
+                }
+            } else {
+                self.advance()?;
+            }
+        }
+    }
+
+    /// Parse a brace-delimited sequence of `key = value;` entries.
+    ///
+    /// When `options.error_recovery` is enabled, a failed entry is recorded
+    /// in `self.errors` and the parser synchronizes to the next statement
+    /// boundary instead of bailing out, so the rest of the object (and the
+    /// rest of the file) still gets a best-effort parse.
+    fn parse_object_body(&mut self) -> CfgppResult<CfgppObject> {
         self.expect(TokenType::LeftBrace)?;
-        let mut object = HashMap::new();
+        let mut object = CfgppObject::new();
 
         while !self.check(TokenType::RightBrace) && !self.is_at_end() {
-            // Parse key
-            let key_token = self.expect_identifier()?;
-            let key = key_token.value.clone();
+            if let Err(e) = self.parse_object_entry(&mut object) {
+                if self.options.error_recovery {
+                    self.errors.push(e);
+                    self.synchronize();
+                } else {
+                    return Err(e);
+                }
+            }
+        }
 
-            // Expect equals
-            self.expect(TokenType::Equals)?;
+        self.expect(TokenType::RightBrace)?;
+        Ok(object)
+    }
 
-            // Parse value
-            let value = self.parse_value()?;
+    /// Parse a single `key = value;` entry into `object`. On failure past the
+    /// key, a `CfgppValue::null()` placeholder is inserted for the key so
+    /// recovery mode doesn't silently drop the slot.
+    fn parse_object_entry(&mut self, object: &mut CfgppObject) -> CfgppResult<()> {
+        let key_raw = self.expect_identifier()?.value.clone();
+        let key = if self.options.intern_keys {
+            self.intern(&key_raw).to_string()
+        } else {
+            key_raw
+        };
 
+        if let Err(e) = self.expect(TokenType::Equals) {
             if !self.options.syntax_only {
-                object.insert(key, value);
+                object.insert(key, CfgppValue::null());
             }
+            return Err(e);
+        }
 
-            // Optional semicolon
-            if self.check(TokenType::Semicolon) {
-                self.advance()?;
+        let value = match self.parse_value() {
+            Ok(value) => value,
+            Err(e) => {
+                if !self.options.syntax_only {
+                    object.insert(key, CfgppValue::null());
+                }
+                return Err(e);
             }
+        };
+
+        if !self.options.syntax_only {
+            object.insert(key, value);
         }
 
-        self.expect(TokenType::RightBrace)?;
+        // Optional semicolon
+        if self.check(TokenType::Semicolon) {
+            self.advance()?;
+        }
 
-        if self.options.syntax_only {
-            Ok(CfgppValue::null())
-        } else {
-            Ok(CfgppValue::object_with_values(object))
+        Ok(())
+    }
+
+    /// Advance past a broken statement until a safe resumption point: a
+    /// `Semicolon` at the current brace/bracket depth, the enclosing
+    /// `RightBrace`/`RightBracket` (left for the caller's `expect` to
+    /// consume), or `Eof`. Every iteration either returns or consumes a
+    /// token, so this can never loop forever; when it stops without
+    /// consuming anything, the caller's own boundary check (`RightBrace`/
+    /// `is_at_end`) terminates the enclosing loop too.
+    fn synchronize(&mut self) {
+        let mut depth: i32 = 0;
+        while !self.is_at_end() {
+            let token_type = match self.current_token() {
+                Ok(t) => t.token_type,
+                Err(_) => return,
+            };
+
+            match token_type {
+                TokenType::Semicolon if depth == 0 => {
+                    let _ = self.advance();
+                    return;
+                }
+                TokenType::LeftBrace | TokenType::LeftBracket => {
+                    depth += 1;
+                    let _ = self.advance();
+                }
+                TokenType::RightBrace | TokenType::RightBracket => {
+                    if depth == 0 {
+                        // Leave the enclosing closer for the caller to consume.
+                        return;
+                    }
+                    depth -= 1;
+                    let _ = self.advance();
+                }
+                TokenType::Eof => return,
+                _ => {
+                    let _ = self.advance();
+                }
+            }
         }
     }
 
     fn parse_array(&mut self) -> CfgppResult<CfgppValue> {
+        if self.options.lazy {
+            return self.parse_raw_block(TokenType::LeftBracket, TokenType::RightBracket);
+        }
+
         self.expect(TokenType::LeftBracket)?;
         let mut array = Vec::new();
 
@@ -239,50 +712,45 @@ impl Parser {
         
         // Check if this is an object definition (identifier followed by {)
         if self.check(TokenType::LeftBrace) {
-            // Parse as named object
-            let mut object = HashMap::new();
-            self.expect(TokenType::LeftBrace)?;
-
-            while !self.check(TokenType::RightBrace) && !self.is_at_end() {
-                let key_token = self.expect_identifier()?;
-                let key = key_token.value.clone();
-
-                self.expect(TokenType::Equals)?;
-                let value = self.parse_value()?;
-
-                if !self.options.syntax_only {
-                    object.insert(key, value);
-                }
-
-                if self.check(TokenType::Semicolon) {
-                    self.advance()?;
-                }
+            if self.options.lazy {
+                return self.parse_raw_block(TokenType::LeftBrace, TokenType::RightBrace);
             }
 
-            self.expect(TokenType::RightBrace)?;
+            let object = self.parse_object_body()?;
 
             if self.options.syntax_only {
                 Ok(CfgppValue::null())
             } else {
                 Ok(CfgppValue::object_with_values(object))
             }
+        } else if self.options.intern_keys {
+            // Treat as enum value; pool repeated enum identifiers the same
+            // way object keys are pooled.
+            Ok(CfgppValue::enum_value(self.intern(&identifier_value).to_string()))
         } else {
-            // Treat as enum value
             Ok(CfgppValue::enum_value(identifier_value))
         }
     }
 
     fn parse_include(&mut self) -> CfgppResult<CfgppValue> {
-        let (include_line, include_column, process_includes) = {
+        let (include_line, include_column, include_start, include_end, process_includes) = {
             let include_token = self.advance()?;
-            (include_token.line, include_token.column, self.options.process_includes)
+            (
+                include_token.line,
+                include_token.column,
+                include_token.position,
+                include_token.end_position,
+                self.options.process_includes,
+            )
         };
-        
+
         if !process_includes {
-            return Err(CfgppError::syntax_error(
+            return Err(CfgppError::syntax_error_spanned(
                 "Include directives are disabled",
                 include_line,
                 include_column,
+                include_start,
+                include_end,
             ));
         }
 
@@ -387,10 +855,12 @@ impl Parser {
             self.advance()
         } else {
             let current = self.current_token()?;
-            Err(CfgppError::syntax_error(
+            Err(CfgppError::syntax_error_spanned(
                 format!("Expected {:?}, found {:?}", expected, current.token_type),
                 current.line,
                 current.column,
+                current.position,
+                current.end_position,
             ))
         }
     }
@@ -494,7 +964,143 @@ mod tests {
 
         let mut parser = Parser::new();
         let result = parser.validate_syntax(input);
-        
+
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_error_recovery_collects_multiple_errors() {
+        let input = r#"
+        {
+            database = {
+                host = ;
+                port = 5432;
+            }
+            cache = {
+                enabled = ;
+            }
+        }
+        "#;
+
+        let mut parser = Parser::with_options(ParserOptions {
+            error_recovery: true,
+            ..ParserOptions::default()
+        });
+
+        let result = parser.parse(input).unwrap();
+        let errors = parser.take_errors();
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| e.is_syntax_error()));
+
+        // The valid sibling field still parses despite the broken ones.
+        assert_eq!(
+            result.get_path("database.port").unwrap().as_integer(),
+            Some(5432)
+        );
+        assert!(result.get_path("database.host").unwrap().is_null());
+    }
+
+    #[test]
+    fn test_key_interning_pool_tracks_distinct_keys() {
+        let mut config = String::from("{\nroot = {\n");
+        for i in 0..200 {
+            config.push_str(&format!(
+                "    server_{} = {{ host = \"h{}\"; port = {}; enabled = true; }}\n",
+                i, i, i
+            ));
+        }
+        config.push_str("}\n}\n");
+
+        let mut parser = Parser::new();
+        let result = parser.parse(&config).unwrap();
+
+        assert_eq!(result.get("root").unwrap().len(), 200);
+        // 200 distinct "server_N" keys, plus "root" and the 3 distinct
+        // field names repeated across every block ("host", "port", "enabled").
+        assert_eq!(parser.interned_key_count(), 204);
+    }
+
+    #[test]
+    fn test_lazy_mode_defers_parsing_until_forced() {
+        let input = r#"
+        root {
+            host = "localhost";
+            port = 5432;
+        }
+        "#;
+
+        let mut parser = Parser::with_options(ParserOptions {
+            lazy: true,
+            ..ParserOptions::default()
+        });
+        let result = parser.parse(input).unwrap();
+
+        assert!(result.is_raw());
+        assert!(result.as_raw().unwrap().trim_start().starts_with('{'));
+
+        let forced = result.force().unwrap();
+        assert_eq!(forced.get("host").unwrap().as_string(), Some("localhost"));
+        assert_eq!(forced.get("port").unwrap().as_integer(), Some(5432));
+    }
+
+    #[test]
+    fn test_get_path_owned_forces_through_raw_blocks() {
+        let input = r#"
+        root {
+            host = "localhost";
+            port = 5432;
+        }
+        "#;
+
+        let mut parser = Parser::with_options(ParserOptions {
+            lazy: true,
+            ..ParserOptions::default()
+        });
+        let result = parser.parse(input).unwrap();
+
+        assert_eq!(
+            result.get_path_owned("host").unwrap().as_string().map(str::to_string),
+            Some("localhost".to_string())
+        );
+    }
+
+    #[test]
+    fn test_large_integer_literal_parses_as_uinteger() {
+        let input = "root { value = 18446744073709551615; }";
+        let mut parser = Parser::new();
+        let result = parser.parse(input).unwrap();
+
+        assert_eq!(result.get("value").unwrap().as_uinteger(), Some(u64::MAX));
+    }
+
+    #[test]
+    fn test_sized_integer_suffix_parses_with_width() {
+        let input = r#"root { small = 255u8; wide = 5432u16; note = 3.14f32; }"#;
+        let mut parser = Parser::new();
+        let result = parser.parse(input).unwrap();
+
+        assert_eq!(
+            result.get("small").unwrap().as_sized_integer(),
+            Some((255, 8, false))
+        );
+        assert_eq!(
+            result.get("wide").unwrap().as_sized_integer(),
+            Some((5432, 16, false))
+        );
+        // "f32" narrows precision but stays a plain Double.
+        let note = result.get("note").unwrap();
+        assert!(note.is_double());
+        assert_eq!(note.as_double(), Some(3.14f32 as f64));
+    }
+
+    #[test]
+    fn test_sized_integer_suffix_rejects_out_of_range_literal() {
+        let input = r#"root { value = 300u8; }"#;
+        let mut parser = Parser::new();
+        let err = parser.parse(input).unwrap_err();
+
+        assert!(err.is_syntax_error());
+        assert!(err.to_string().contains("out of range"));
+    }
 }
@@ -0,0 +1,684 @@
+//! JSONPath-style query engine over [`CfgppValue`] trees.
+//!
+//! [`CfgppValue::query`] and [`CfgppValue::query_mut`] go beyond
+//! [`CfgppValue::get_path`]'s single dotted chain: an expression compiles to
+//! a sequence of segments, each mapping a working set of node references to
+//! the next one. `Child`/`Index` segments narrow to at most one match per
+//! node; `Wildcard`, `RecursiveDescent`, `Slice`, and `Filter` fan out to
+//! zero or more. A segment that finds nothing on a given node simply drops
+//! it, rather than erroring - only a malformed expression itself is an
+//! error.
+//!
+//! Supported syntax:
+//! - `$` - optional root marker
+//! - `.name` / `['name']` / `["name"]` - child by key
+//! - `[n]` - index (negative counts from the end)
+//! - `[start:end:step]` - slice (any bound may be omitted; non-positive step
+//!   is treated as `1`)
+//! - `*` / `[*]` - wildcard over all children/elements
+//! - `..` - recursive descent: visits the current node and every descendant
+//! - `[?(@.field OP literal)]` / `[?(@ OP literal)]` - filter, where `OP` is
+//!   one of `== != < <= > >=`
+
+use crate::error::{CfgppError, CfgppResult};
+use crate::value::CfgppValue;
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Child(String),
+    Index(i64),
+    Slice(Option<i64>, Option<i64>, i64),
+    Wildcard,
+    RecursiveDescent,
+    Filter(Predicate),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone)]
+struct Predicate {
+    /// `None` means the predicate compares against `@` itself, not a field.
+    field: Option<String>,
+    op: CompareOp,
+    literal: CfgppValue,
+}
+
+impl CfgppValue {
+    /// Evaluate a JSONPath-like `expr` against this value, returning every
+    /// matching node in traversal order: array elements by index, object
+    /// members in the object's iteration order, which is document order
+    /// only with the `preserve_order` feature. Returns an error only if `expr`
+    /// itself fails to compile; a well-formed expression that simply matches
+    /// nothing yields an empty vector.
+    pub fn query(&self, expr: &str) -> CfgppResult<Vec<&CfgppValue>> {
+        let segments = compile(expr)?;
+        let mut current: Vec<&CfgppValue> = vec![self];
+        for segment in &segments {
+            current = apply_segment(current, segment);
+        }
+        Ok(current)
+    }
+
+    /// Like [`CfgppValue::query`], but returns mutable references so matches
+    /// can be edited in bulk. One difference from `query`: `..` never
+    /// includes a non-empty object/array alongside its own contents, since
+    /// holding `&mut` to both a container and something inside it would
+    /// alias; a non-empty container's descendants are still visited in
+    /// the same order as `query`, the container reference itself is just skipped.
+    pub fn query_mut(&mut self, expr: &str) -> CfgppResult<Vec<&mut CfgppValue>> {
+        let segments = compile(expr)?;
+        let mut current: Vec<&mut CfgppValue> = vec![self];
+        for segment in &segments {
+            current = apply_segment_mut(current, segment);
+        }
+        Ok(current)
+    }
+}
+
+fn compile(expr: &str) -> CfgppResult<Vec<Segment>> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut idx = if chars.first() == Some(&'$') { 1 } else { 0 };
+    let mut segments = Vec::new();
+
+    while idx < chars.len() {
+        match chars[idx] {
+            '.' => {
+                idx += 1;
+                if chars.get(idx) == Some(&'.') {
+                    idx += 1;
+                    segments.push(Segment::RecursiveDescent);
+                } else {
+                    let name = read_name(&chars, &mut idx);
+                    if name.is_empty() {
+                        return Err(CfgppError::parse_error(format!(
+                            "Expected a name after '.' in query: {}",
+                            expr
+                        )));
+                    }
+                    segments.push(to_name_segment(name));
+                }
+            }
+            '[' => segments.push(parse_bracket(&chars, &mut idx, expr)?),
+            _ => {
+                let name = read_name(&chars, &mut idx);
+                if name.is_empty() {
+                    return Err(CfgppError::parse_error(format!(
+                        "Unexpected character at position {} in query: {}",
+                        idx, expr
+                    )));
+                }
+                segments.push(to_name_segment(name));
+            }
+        }
+    }
+
+    Ok(segments)
+}
+
+fn to_name_segment(name: String) -> Segment {
+    if name == "*" {
+        Segment::Wildcard
+    } else {
+        Segment::Child(name)
+    }
+}
+
+fn read_name(chars: &[char], idx: &mut usize) -> String {
+    if chars.get(*idx) == Some(&'*') {
+        *idx += 1;
+        return "*".to_string();
+    }
+
+    let start = *idx;
+    while let Some(&c) = chars.get(*idx) {
+        if c == '.' || c == '[' || c == ']' {
+            break;
+        }
+        *idx += 1;
+    }
+    chars[start..*idx].iter().collect()
+}
+
+fn parse_bracket(chars: &[char], idx: &mut usize, expr: &str) -> CfgppResult<Segment> {
+    *idx += 1; // consume '['
+
+    match chars.get(*idx) {
+        Some('?') => {
+            *idx += 1;
+            if chars.get(*idx) != Some(&'(') {
+                return Err(CfgppError::parse_error(format!(
+                    "Expected '(' after '?' in query: {}",
+                    expr
+                )));
+            }
+            *idx += 1;
+            let start = *idx;
+            while chars.get(*idx).is_some() && chars[*idx] != ')' {
+                *idx += 1;
+            }
+            if chars.get(*idx) != Some(&')') {
+                return Err(CfgppError::parse_error(format!(
+                    "Unterminated filter predicate in query: {}",
+                    expr
+                )));
+            }
+            let inner: String = chars[start..*idx].iter().collect();
+            *idx += 1; // consume ')'
+            if chars.get(*idx) != Some(&']') {
+                return Err(CfgppError::parse_error(format!(
+                    "Expected ']' to close filter segment in query: {}",
+                    expr
+                )));
+            }
+            *idx += 1; // consume ']'
+            Ok(Segment::Filter(parse_predicate(&inner)?))
+        }
+        Some('*') => {
+            *idx += 1;
+            expect_close_bracket(chars, idx, expr)?;
+            Ok(Segment::Wildcard)
+        }
+        Some(&quote) if quote == '\'' || quote == '"' => {
+            *idx += 1;
+            let start = *idx;
+            while chars.get(*idx).is_some() && chars[*idx] != quote {
+                *idx += 1;
+            }
+            if chars.get(*idx) != Some(&quote) {
+                return Err(CfgppError::parse_error(format!(
+                    "Unterminated quoted key in query: {}",
+                    expr
+                )));
+            }
+            let name: String = chars[start..*idx].iter().collect();
+            *idx += 1; // consume closing quote
+            expect_close_bracket(chars, idx, expr)?;
+            Ok(Segment::Child(name))
+        }
+        _ => {
+            let start = *idx;
+            while chars.get(*idx).is_some() && chars[*idx] != ']' {
+                *idx += 1;
+            }
+            let content: String = chars[start..*idx].iter().collect();
+            expect_close_bracket(chars, idx, expr)?;
+            parse_index_or_slice(&content, expr)
+        }
+    }
+}
+
+fn expect_close_bracket(chars: &[char], idx: &mut usize, expr: &str) -> CfgppResult<()> {
+    if chars.get(*idx) != Some(&']') {
+        return Err(CfgppError::parse_error(format!(
+            "Expected ']' to close bracket segment in query: {}",
+            expr
+        )));
+    }
+    *idx += 1;
+    Ok(())
+}
+
+fn parse_index_or_slice(content: &str, expr: &str) -> CfgppResult<Segment> {
+    if content.contains(':') {
+        let parts: Vec<&str> = content.split(':').collect();
+        let bound = |s: &str| -> CfgppResult<Option<i64>> {
+            let s = s.trim();
+            if s.is_empty() {
+                Ok(None)
+            } else {
+                s.parse::<i64>()
+                    .map(Some)
+                    .map_err(|_| CfgppError::parse_error(format!("Invalid slice bound '{}' in query: {}", s, expr)))
+            }
+        };
+        let start = bound(parts.first().copied().unwrap_or(""))?;
+        let end = bound(parts.get(1).copied().unwrap_or(""))?;
+        let step = match parts.get(2).map(|s| s.trim()) {
+            Some(s) if !s.is_empty() => s
+                .parse::<i64>()
+                .map_err(|_| CfgppError::parse_error(format!("Invalid slice step '{}' in query: {}", s, expr)))?,
+            _ => 1,
+        };
+        Ok(Segment::Slice(start, end, step))
+    } else {
+        let index = content
+            .trim()
+            .parse::<i64>()
+            .map_err(|_| CfgppError::parse_error(format!("Invalid index '{}' in query: {}", content, expr)))?;
+        Ok(Segment::Index(index))
+    }
+}
+
+fn parse_predicate(inner: &str) -> CfgppResult<Predicate> {
+    const OPS: [(&str, CompareOp); 6] = [
+        ("==", CompareOp::Eq),
+        ("!=", CompareOp::Ne),
+        ("<=", CompareOp::Le),
+        (">=", CompareOp::Ge),
+        ("<", CompareOp::Lt),
+        (">", CompareOp::Gt),
+    ];
+
+    let mut found = None;
+    'scan: for (i, _) in inner.char_indices() {
+        for (op_str, op) in OPS {
+            if inner[i..].starts_with(op_str) {
+                found = Some((i, op_str.len(), op));
+                break 'scan;
+            }
+        }
+    }
+
+    let (start, len, op) = found
+        .ok_or_else(|| CfgppError::parse_error(format!("Invalid filter predicate: {}", inner)))?;
+    let lhs = inner[..start].trim();
+    let rhs = inner[start + len..].trim();
+
+    let field = if lhs == "@" {
+        None
+    } else if let Some(name) = lhs.strip_prefix("@.") {
+        Some(name.trim().to_string())
+    } else {
+        return Err(CfgppError::parse_error(format!(
+            "Filter predicate must start with '@' or '@.field': {}",
+            inner
+        )));
+    };
+
+    Ok(Predicate {
+        field,
+        op,
+        literal: parse_literal(rhs),
+    })
+}
+
+fn parse_literal(raw: &str) -> CfgppValue {
+    let raw = raw.trim();
+
+    if raw.len() >= 2 {
+        let quote = raw.chars().next().unwrap();
+        if (quote == '\'' || quote == '"') && raw.ends_with(quote) {
+            return CfgppValue::string(raw[1..raw.len() - 1].to_string());
+        }
+    }
+
+    match raw {
+        "true" => return CfgppValue::boolean(true),
+        "false" => return CfgppValue::boolean(false),
+        "null" => return CfgppValue::null(),
+        _ => {}
+    }
+
+    if let Ok(i) = raw.parse::<i64>() {
+        return CfgppValue::integer(i);
+    }
+    if let Ok(u) = raw.parse::<u64>() {
+        return CfgppValue::uinteger(u);
+    }
+    if let Ok(d) = raw.parse::<f64>() {
+        return CfgppValue::double(d);
+    }
+
+    CfgppValue::string(raw.to_string())
+}
+
+fn as_f64(value: &CfgppValue) -> Option<f64> {
+    match value {
+        CfgppValue::Integer(i) => Some(*i as f64),
+        CfgppValue::UInteger(u) => Some(*u as f64),
+        CfgppValue::SizedInteger { value, signed: true, .. } => Some(*value as f64),
+        CfgppValue::SizedInteger { value, signed: false, .. } => Some(*value as u64 as f64),
+        CfgppValue::Double(d) => Some(*d),
+        _ => None,
+    }
+}
+
+fn compare(value: &CfgppValue, op: CompareOp, literal: &CfgppValue) -> bool {
+    match op {
+        CompareOp::Eq => match (as_f64(value), as_f64(literal)) {
+            (Some(a), Some(b)) => a == b,
+            _ => value == literal,
+        },
+        CompareOp::Ne => !compare(value, CompareOp::Eq, literal),
+        CompareOp::Lt | CompareOp::Le | CompareOp::Gt | CompareOp::Ge => {
+            if let (Some(a), Some(b)) = (as_f64(value), as_f64(literal)) {
+                match op {
+                    CompareOp::Lt => a < b,
+                    CompareOp::Le => a <= b,
+                    CompareOp::Gt => a > b,
+                    CompareOp::Ge => a >= b,
+                    _ => unreachable!(),
+                }
+            } else if let (Some(a), Some(b)) = (value.as_string(), literal.as_string()) {
+                match op {
+                    CompareOp::Lt => a < b,
+                    CompareOp::Le => a <= b,
+                    CompareOp::Gt => a > b,
+                    CompareOp::Ge => a >= b,
+                    _ => unreachable!(),
+                }
+            } else {
+                false
+            }
+        }
+    }
+}
+
+fn matches_predicate(node: &CfgppValue, pred: &Predicate) -> bool {
+    let candidate = match &pred.field {
+        Some(field) => match node.get(field) {
+            Some(v) => v,
+            None => return false,
+        },
+        None => node,
+    };
+    compare(candidate, pred.op, &pred.literal)
+}
+
+fn resolve_index(len: usize, index: i64) -> Option<usize> {
+    if index >= 0 {
+        let i = index as usize;
+        if i < len {
+            Some(i)
+        } else {
+            None
+        }
+    } else {
+        let from_end = (-index) as usize;
+        if from_end <= len {
+            Some(len - from_end)
+        } else {
+            None
+        }
+    }
+}
+
+fn normalize_slice_bound(bound: Option<i64>, default: usize, len: i64) -> usize {
+    match bound {
+        Some(i) => {
+            let i = if i < 0 { len + i } else { i };
+            i.clamp(0, len) as usize
+        }
+        None => default,
+    }
+}
+
+fn apply_segment<'a>(nodes: Vec<&'a CfgppValue>, segment: &Segment) -> Vec<&'a CfgppValue> {
+    match segment {
+        Segment::Child(name) => nodes.into_iter().filter_map(|n| n.get(name)).collect(),
+        Segment::Index(i) => nodes
+            .into_iter()
+            .filter_map(|n| n.as_array().and_then(|arr| resolve_index(arr.len(), *i)).and_then(|idx| n.get_index(idx)))
+            .collect(),
+        Segment::Slice(start, end, step) => nodes
+            .into_iter()
+            .flat_map(|n| slice_of(n, *start, *end, *step))
+            .collect(),
+        Segment::Wildcard => nodes.into_iter().flat_map(children_of).collect(),
+        Segment::RecursiveDescent => {
+            let mut out = Vec::new();
+            for node in nodes {
+                collect_descendants(node, &mut out);
+            }
+            out
+        }
+        Segment::Filter(pred) => nodes
+            .into_iter()
+            .flat_map(children_of)
+            .filter(|child| matches_predicate(child, pred))
+            .collect(),
+    }
+}
+
+fn children_of(node: &CfgppValue) -> Vec<&CfgppValue> {
+    match node {
+        CfgppValue::Object(obj) => obj.values().collect(),
+        CfgppValue::Array(arr) => arr.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn slice_of(node: &CfgppValue, start: Option<i64>, end: Option<i64>, step: i64) -> Vec<&CfgppValue> {
+    let arr = match node.as_array() {
+        Some(arr) => arr,
+        None => return Vec::new(),
+    };
+    let len = arr.len() as i64;
+    let step = if step > 0 { step as usize } else { 1 };
+    let start_idx = normalize_slice_bound(start, 0, len);
+    let end_idx = normalize_slice_bound(end, arr.len(), len);
+
+    let mut result = Vec::new();
+    let mut i = start_idx;
+    while i < end_idx {
+        if let Some(v) = arr.get(i) {
+            result.push(v);
+        }
+        i += step;
+    }
+    result
+}
+
+fn collect_descendants<'a>(node: &'a CfgppValue, out: &mut Vec<&'a CfgppValue>) {
+    out.push(node);
+    match node {
+        CfgppValue::Object(obj) => {
+            for value in obj.values() {
+                collect_descendants(value, out);
+            }
+        }
+        CfgppValue::Array(arr) => {
+            for value in arr {
+                collect_descendants(value, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn apply_segment_mut<'a>(nodes: Vec<&'a mut CfgppValue>, segment: &Segment) -> Vec<&'a mut CfgppValue> {
+    match segment {
+        Segment::Child(name) => nodes.into_iter().filter_map(|n| n.get_mut(name)).collect(),
+        Segment::Index(i) => nodes
+            .into_iter()
+            .filter_map(|n| {
+                let resolved = n.as_array().and_then(|arr| resolve_index(arr.len(), *i));
+                resolved.and_then(|idx| n.get_index_mut(idx))
+            })
+            .collect(),
+        Segment::Slice(start, end, step) => nodes
+            .into_iter()
+            .flat_map(|n| slice_of_mut(n, *start, *end, *step))
+            .collect(),
+        Segment::Wildcard => nodes.into_iter().flat_map(children_of_mut).collect(),
+        Segment::RecursiveDescent => {
+            let mut out = Vec::new();
+            for node in nodes {
+                collect_descendants_mut(node, &mut out);
+            }
+            out
+        }
+        Segment::Filter(pred) => nodes
+            .into_iter()
+            .flat_map(children_of_mut)
+            .filter(|child| matches_predicate(&**child, pred))
+            .collect(),
+    }
+}
+
+fn children_of_mut(node: &mut CfgppValue) -> Vec<&mut CfgppValue> {
+    match node {
+        CfgppValue::Object(obj) => obj.values_mut().collect(),
+        CfgppValue::Array(arr) => arr.iter_mut().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn slice_of_mut(node: &mut CfgppValue, start: Option<i64>, end: Option<i64>, step: i64) -> Vec<&mut CfgppValue> {
+    let len = match node.as_array() {
+        Some(arr) => arr.len(),
+        None => return Vec::new(),
+    };
+    let step = if step > 0 { step as usize } else { 1 };
+    let start_idx = normalize_slice_bound(start, 0, len as i64);
+    let end_idx = normalize_slice_bound(end, len, len as i64);
+
+    let arr = match node.as_array_mut() {
+        Some(arr) => arr,
+        None => return Vec::new(),
+    };
+
+    arr.iter_mut()
+        .enumerate()
+        .filter(|(i, _)| *i >= start_idx && *i < end_idx && (*i - start_idx) % step == 0)
+        .map(|(_, v)| v)
+        .collect()
+}
+
+// Unlike `collect_descendants`, this can't push a non-empty Object/Array
+// node and then also hand out `&mut` borrows into its children: the two
+// would alias, since the container owns its children's storage, and the
+// borrow checker rightly refuses to let a caller hold a mutable reference
+// to a container while also holding one into its contents (it could drop
+// or replace the container and invalidate the child reference). So a
+// non-empty container's descendants are collected recursively in the same
+// order as the immutable traversal, but the container itself is
+// only pushed when it has no children to alias with.
+fn collect_descendants_mut<'a>(node: &'a mut CfgppValue, out: &mut Vec<&'a mut CfgppValue>) {
+    let has_children = matches!(
+        node,
+        CfgppValue::Object(obj) if !obj.is_empty()
+    ) || matches!(
+        node,
+        CfgppValue::Array(arr) if !arr.is_empty()
+    );
+    if !has_children {
+        out.push(node);
+        return;
+    }
+    match node {
+        CfgppValue::Object(obj) => {
+            for value in obj.values_mut() {
+                collect_descendants_mut(value, out);
+            }
+        }
+        CfgppValue::Array(arr) => {
+            for value in arr {
+                collect_descendants_mut(value, out);
+            }
+        }
+        _ => unreachable!("has_children is only true for non-empty Object/Array"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn parse(input: &str) -> CfgppValue {
+        Parser::new().parse(input).unwrap()
+    }
+
+    #[test]
+    fn test_wildcard_collects_all_array_elements() {
+        let value = parse("root { ports = [80, 443, 8080]; }");
+        let matches = value.query("$.ports[*]").unwrap();
+        assert_eq!(matches.len(), 3);
+        assert_eq!(matches[1].as_integer(), Some(443));
+    }
+
+    #[test]
+    fn test_recursive_descent_finds_nested_field() {
+        let value = parse(
+            r#"root {
+                web = { port = 8080; }
+                api = { port = 8081; }
+            }"#,
+        );
+        let mut ports: Vec<i64> = value
+            .query("$..port")
+            .unwrap()
+            .into_iter()
+            .filter_map(|v| v.as_integer())
+            .collect();
+        ports.sort();
+        assert_eq!(ports, vec![8080, 8081]);
+    }
+
+    #[test]
+    fn test_filter_predicate_selects_matching_elements() {
+        let value = parse(
+            r#"root {
+                servers = [
+                    { name = "a"; port = 8000; },
+                    { name = "b"; port = 9000; }
+                ];
+            }"#,
+        );
+        let matches = value.query("$.servers[?(@.port > 8500)]").unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].get("name").and_then(|v| v.as_string()), Some("b"));
+    }
+
+    #[test]
+    fn test_slice_respects_bounds_and_step() {
+        let value = parse("root { numbers = [0, 1, 2, 3, 4, 5]; }");
+        let matches = value.query("$.numbers[1:5:2]").unwrap();
+        let values: Vec<i64> = matches.into_iter().filter_map(|v| v.as_integer()).collect();
+        assert_eq!(values, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_unmatched_segment_yields_empty_set_not_error() {
+        let value = parse("root { host = \"localhost\"; }");
+        let matches = value.query("$.missing.deeper").unwrap();
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn test_query_mut_allows_bulk_edits() {
+        let mut value = parse("root { servers = [ { port = 1; }, { port = 2; } ]; }");
+        for node in value.query_mut("$.servers[*].port").unwrap() {
+            *node = CfgppValue::integer(node.as_integer().unwrap() + 1000);
+        }
+        let ports: Vec<i64> = value
+            .query("$.servers[*].port")
+            .unwrap()
+            .into_iter()
+            .filter_map(|v| v.as_integer())
+            .collect();
+        assert_eq!(ports, vec![1001, 1002]);
+    }
+
+    #[test]
+    fn test_query_mut_recursive_descent_edits_every_leaf() {
+        let mut value = parse(
+            r#"root {
+                web = { port = 8080; }
+                api = { port = 8081; }
+            }"#,
+        );
+        for node in value.query_mut("$..").unwrap() {
+            if let Some(port) = node.as_integer() {
+                *node = CfgppValue::integer(port + 1);
+            }
+        }
+        let mut ports: Vec<i64> = value
+            .query("$..")
+            .unwrap()
+            .into_iter()
+            .filter_map(|v| v.as_integer())
+            .collect();
+        ports.sort();
+        assert_eq!(ports, vec![8081, 8082]);
+    }
+}
@@ -4,13 +4,13 @@
 use jsonschema::{JSONSchema, ValidationError as JsonValidationError};
 use crate::{
     error::{CfgppError, CfgppResult},
-    value::CfgppValue,
+    value::{CfgppObject, CfgppValue},
 };
 use std::collections::HashMap;
 use regex::Regex;
 
 /// Schema definition for CFG++ values
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Schema {
     /// Type definitions (name -> type)
     type_defs: HashMap<String, TypeDefinition>,
@@ -18,8 +18,182 @@ pub struct Schema {
     enum_defs: HashMap<String, Vec<String>>,
     /// Object schemas (name -> field definitions)
     object_schemas: HashMap<String, HashMap<String, FieldDefinition>>,
+    /// Tuple schemas (name -> positional slot definitions), validated
+    /// against a `CfgppValue::Array` by index instead of by key.
+    tuple_schemas: HashMap<String, Vec<FieldDefinition>>,
     /// Root schema definition
     root_schema: Option<TypeDefinition>,
+    /// Custom validators invoked by `Constraint::Custom(name)`.
+    validators: ValidatorRegistry,
+    /// Named format validators invoked by `Constraint::NamedFormat(name)`.
+    format_validators: FormatValidatorRegistry,
+}
+
+/// Context passed to a custom validator registered in a [`ValidatorRegistry`]:
+/// the path of the value currently being checked, and a reference to the
+/// root value passed to [`Schema::validate`], so a validator isn't limited
+/// to the one field it's attached to and can do cross-field checks (e.g.
+/// "end_date must be after start_date").
+pub struct ValidationContext<'a> {
+    pub path: &'a str,
+    pub root: &'a CfgppValue,
+}
+
+/// Registry of named custom validators, looked up when `validate_constraint`
+/// hits a `Constraint::Custom(name)`. Mirrors how the `validator` crate moved
+/// custom validation to user-supplied closures with optional context
+/// arguments, instead of `Custom` being a permanent no-op.
+#[derive(Default)]
+pub struct ValidatorRegistry {
+    validators: HashMap<String, Box<dyn Fn(&CfgppValue, &ValidationContext) -> Result<(), String>>>,
+}
+
+impl ValidatorRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a validator under `name`.
+    pub fn register<F>(&mut self, name: impl Into<String>, validator: F)
+    where
+        F: Fn(&CfgppValue, &ValidationContext) -> Result<(), String> + 'static,
+    {
+        self.validators.insert(name.into(), Box::new(validator));
+    }
+
+    fn get(&self, name: &str) -> Option<&dyn Fn(&CfgppValue, &ValidationContext) -> Result<(), String>> {
+        self.validators.get(name).map(|validator| validator.as_ref())
+    }
+}
+
+impl std::fmt::Debug for ValidatorRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValidatorRegistry")
+            .field("registered", &self.validators.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// A pluggable validator for a named string format invoked when
+/// `Constraint::NamedFormat(name)` doesn't name one of the closed
+/// [`FormatKind`] variants. Unlike `FormatKind`, which maps onto JSON
+/// Schema's standard `format` keyword values, this lets schema authors
+/// register domain-specific formats (national ID check-digit algorithms and
+/// the like) without forking the crate.
+pub trait FormatValidator: Send + Sync {
+    /// Return `Ok(())` if `value` satisfies the format, or `Err(reason)`
+    /// naming which check failed.
+    fn validate(&self, value: &str) -> Result<(), String>;
+}
+
+/// Registry of named format validators, looked up when `validate_constraint`
+/// hits a `Constraint::NamedFormat(name)`. Pre-populated with `"cpf"` and
+/// `"cnpj"` check-digit validators for Brazilian national IDs;
+/// [`Schema::register_format_validator`] adds more.
+pub struct FormatValidatorRegistry {
+    validators: HashMap<String, Box<dyn FormatValidator>>,
+}
+
+impl FormatValidatorRegistry {
+    fn with_builtins() -> Self {
+        let mut registry = Self { validators: HashMap::new() };
+        registry.register("cpf", CpfValidator);
+        registry.register("cnpj", CnpjValidator);
+        registry
+    }
+
+    /// Register a validator under `name`.
+    pub fn register(&mut self, name: impl Into<String>, validator: impl FormatValidator + 'static) {
+        self.validators.insert(name.into(), Box::new(validator));
+    }
+
+    fn get(&self, name: &str) -> Option<&dyn FormatValidator> {
+        self.validators.get(name).map(|validator| validator.as_ref())
+    }
+}
+
+impl std::fmt::Debug for FormatValidatorRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FormatValidatorRegistry")
+            .field("registered", &self.validators.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Strips everything but ASCII digits from `value`.
+fn digits_only(value: &str) -> Vec<u32> {
+    value.chars().filter_map(|c| c.to_digit(10)).collect()
+}
+
+/// Computes a single Brazilian (CPF/CNPJ-style) check digit: multiply
+/// `digits` by the positionally-paired `weights`, sum, reduce `sum % 11`,
+/// then map a remainder `< 2` to `0` and anything else to `11 - remainder`.
+fn brazilian_check_digit(digits: &[u32], weights: &[u32]) -> u32 {
+    let sum: u32 = digits.iter().zip(weights).map(|(d, w)| d * w).sum();
+    let remainder = sum % 11;
+    if remainder < 2 {
+        0
+    } else {
+        11 - remainder
+    }
+}
+
+/// Validates an 11-digit Brazilian CPF via its two trailing check digits.
+struct CpfValidator;
+
+impl FormatValidator for CpfValidator {
+    fn validate(&self, value: &str) -> Result<(), String> {
+        let digits = digits_only(value);
+        if digits.len() != 11 {
+            return Err(format!("CPF must have 11 digits, found {}", digits.len()));
+        }
+        if digits.iter().all(|&d| d == digits[0]) {
+            return Err("CPF cannot have all identical digits".to_string());
+        }
+
+        let first = brazilian_check_digit(&digits[0..9], &[10, 9, 8, 7, 6, 5, 4, 3, 2]);
+        if first != digits[9] {
+            return Err("CPF first check digit is invalid".to_string());
+        }
+
+        let second = brazilian_check_digit(&digits[0..10], &[11, 10, 9, 8, 7, 6, 5, 4, 3, 2]);
+        if second != digits[10] {
+            return Err("CPF second check digit is invalid".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// Validates a 14-digit Brazilian CNPJ via its two trailing check digits.
+struct CnpjValidator;
+
+impl FormatValidator for CnpjValidator {
+    fn validate(&self, value: &str) -> Result<(), String> {
+        let digits = digits_only(value);
+        if digits.len() != 14 {
+            return Err(format!("CNPJ must have 14 digits, found {}", digits.len()));
+        }
+        if digits.iter().all(|&d| d == digits[0]) {
+            return Err("CNPJ cannot have all identical digits".to_string());
+        }
+
+        const WEIGHTS_12: [u32; 12] = [5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2];
+        const WEIGHTS_13: [u32; 13] = [6, 5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2];
+
+        let first = brazilian_check_digit(&digits[0..12], &WEIGHTS_12);
+        if first != digits[12] {
+            return Err("CNPJ first check digit is invalid".to_string());
+        }
+
+        let second = brazilian_check_digit(&digits[0..13], &WEIGHTS_13);
+        if second != digits[13] {
+            return Err("CNPJ second check digit is invalid".to_string());
+        }
+
+        Ok(())
+    }
 }
 
 /// Type definition for schema validation
@@ -34,10 +208,18 @@ pub enum TypeDefinition {
     /// Collection types
     Array(Box<TypeDefinition>),
     Object(String), // Reference to object schema
+    /// Reference to a tuple schema: a `CfgppValue::Array` validated by
+    /// position rather than by key, the way a Rust tuple struct's fields are
+    /// addressed by index instead of by name. See [`Schema::add_tuple_schema`].
+    Tuple(String),
     /// Custom types
     Enum(String), // Reference to enum definition
-    /// Union types
+    /// Union types - `anyOf` semantics: valid if at least one member matches.
     Union(Vec<TypeDefinition>),
+    /// Discriminated union - `oneOf` semantics: valid only if *exactly one*
+    /// member matches; zero or multiple matches are both errors. Distinct
+    /// from `Union`, which accepts the first matching member.
+    OneOf(Vec<TypeDefinition>),
     /// Optional type
     Optional(Box<TypeDefinition>),
 }
@@ -49,6 +231,34 @@ pub struct FieldDefinition {
     pub required: bool,
     pub default_value: Option<CfgppValue>,
     pub constraints: Vec<Constraint>,
+    /// Earlier names this field was known by. [`Schema::check_compatibility`]
+    /// matches a field against its counterpart in the other schema version by
+    /// name OR by any alias, so a rename doesn't read as an unrelated
+    /// add/remove pair.
+    pub aliases: Vec<String>,
+    /// When set, this field's presence/requiredness is conditional on the
+    /// sibling fields of the object it belongs to, e.g. "`cert_path` is
+    /// required only when `tls_enabled == true`". See [`FieldDefinition::when`].
+    pub guard: Option<FieldCondition>,
+}
+
+/// A predicate over the sibling fields of the object a guarded
+/// [`FieldDefinition`] belongs to, evaluated by [`Schema::validate_type`]
+/// against that enclosing object - not the root document - so `field_path`
+/// is written relative to it (dotted, e.g. `"tls.enabled"`, to reach into a
+/// nested object).
+#[derive(Debug, Clone)]
+pub enum FieldCondition {
+    /// True if `field_path` resolves to a value equal to the given one.
+    Eq(String, CfgppValue),
+    /// True if `field_path` resolves to any value at all.
+    Exists(String),
+    /// True if both sub-conditions are true.
+    And(Box<FieldCondition>, Box<FieldCondition>),
+    /// True if either sub-condition is true.
+    Or(Box<FieldCondition>, Box<FieldCondition>),
+    /// True if the sub-condition is false.
+    Not(Box<FieldCondition>),
 }
 
 /// Validation constraints
@@ -62,17 +272,302 @@ pub enum Constraint {
     MaxValue(f64),
     /// Pattern matching
     Pattern(Regex),
+    /// A named string format, e.g. `email` or `uuid`.
+    Format(FormatKind),
+    /// A string format outside the closed `FormatKind` set, looked up in the
+    /// schema's [`FormatValidatorRegistry`] by name (e.g. `"cpf"`, `"cnpj"`).
+    NamedFormat(String),
     /// Custom validation function
     Custom(String), // Function name for custom validation
 }
 
+/// Named string formats checkable via `Constraint::Format`, mirroring JSON
+/// Schema's standard `format` values so a schema round-trips through
+/// [`Schema::to_json_schema`]/[`Schema::from_json_schema`] using the `format`
+/// keyword directly rather than a vendor extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatKind {
+    Email,
+    Uri,
+    Ipv4,
+    Ipv6,
+    Uuid,
+    DateTime,
+    Hostname,
+}
+
+impl FormatKind {
+    /// The JSON Schema `format` keyword value this variant corresponds to.
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Email => "email",
+            Self::Uri => "uri",
+            Self::Ipv4 => "ipv4",
+            Self::Ipv6 => "ipv6",
+            Self::Uuid => "uuid",
+            Self::DateTime => "date-time",
+            Self::Hostname => "hostname",
+        }
+    }
+
+    /// Reverse of [`Self::as_str`]; `None` for any value not among the
+    /// formats this crate understands.
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "email" => Some(Self::Email),
+            "uri" => Some(Self::Uri),
+            "ipv4" => Some(Self::Ipv4),
+            "ipv6" => Some(Self::Ipv6),
+            "uuid" => Some(Self::Uuid),
+            "date-time" => Some(Self::DateTime),
+            "hostname" => Some(Self::Hostname),
+            _ => None,
+        }
+    }
+
+    /// True if `value` satisfies this format.
+    fn matches(&self, value: &str) -> bool {
+        match self {
+            Self::Email => Self::email_regex().is_match(value),
+            Self::Uri => Self::uri_regex().is_match(value),
+            Self::Ipv4 => value.parse::<std::net::Ipv4Addr>().is_ok(),
+            Self::Ipv6 => value.parse::<std::net::Ipv6Addr>().is_ok(),
+            Self::Uuid => Self::uuid_regex().is_match(value),
+            Self::DateTime => Self::date_time_regex().is_match(value),
+            Self::Hostname => Self::hostname_regex().is_match(value),
+        }
+    }
+
+    fn email_regex() -> &'static Regex {
+        static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+        RE.get_or_init(|| Regex::new(r"^[^\s@]+@[^\s@]+\.[^\s@]+$").unwrap())
+    }
+
+    fn uri_regex() -> &'static Regex {
+        static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+        RE.get_or_init(|| Regex::new(r"^[A-Za-z][A-Za-z0-9+.\-]*:\S+$").unwrap())
+    }
+
+    fn uuid_regex() -> &'static Regex {
+        static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+        RE.get_or_init(|| {
+            Regex::new(r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$").unwrap()
+        })
+    }
+
+    fn date_time_regex() -> &'static Regex {
+        static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+        RE.get_or_init(|| {
+            Regex::new(r"(?i)^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?(Z|[+-]\d{2}:\d{2})$").unwrap()
+        })
+    }
+
+    fn hostname_regex() -> &'static Regex {
+        static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+        RE.get_or_init(|| {
+            Regex::new(r"(?i)^[a-z0-9]([a-z0-9-]{0,61}[a-z0-9])?(\.[a-z0-9]([a-z0-9-]{0,61}[a-z0-9])?)*$").unwrap()
+        })
+    }
+}
+
+impl std::fmt::Display for FormatKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A location within a validated `CfgppValue` document, rendered in JSON
+/// Path syntax (e.g. `$.servers[2].port`) so editor integrations that
+/// already understand JSON Path can underline the offending value without
+/// this crate inventing its own location format. [`Schema::check`]'s
+/// diagnostics describe a schema *definition* site rather than a document
+/// location, so they carry an opaque path built from [`JsonPath::opaque`]
+/// instead of one rooted at [`JsonPath::root`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPath(String);
+
+impl JsonPath {
+    /// The root of a document: `$`.
+    pub fn root() -> Self {
+        Self("$".to_string())
+    }
+
+    /// An arbitrary, non-document-rooted path, e.g. a schema-definition
+    /// location reported by [`Schema::check`].
+    pub fn opaque(path: impl Into<String>) -> Self {
+        Self(path.into())
+    }
+
+    /// Append a named field: `self.foo`.
+    pub fn field(&self, name: &str) -> Self {
+        Self(format!("{}.{}", self.0, name))
+    }
+
+    /// Append an array index: `self[i]`.
+    pub fn index(&self, i: usize) -> Self {
+        Self(format!("{}[{}]", self.0, i))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PartialEq<str> for JsonPath {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for JsonPath {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// A stable, machine-matchable classification of a [`ValidationDiagnostic`],
+/// for callers (editor integrations, CI checks) that need to branch on the
+/// kind of problem instead of pattern-matching `message`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// A required field, tuple slot, or guarded field was absent.
+    MissingRequiredField,
+    /// The value's shape doesn't match the expected type, enum, union, or
+    /// oneOf branch.
+    TypeMismatch,
+    /// A `Constraint::Format`/`Constraint::NamedFormat` check failed.
+    FormatInvalid,
+    /// A field is present that the object schema doesn't define.
+    UnknownField,
+    /// A `MinLength`/`MaxLength`/`MinValue`/`MaxValue`/`Pattern` constraint
+    /// failed.
+    ConstraintViolation,
+    /// A schema references an object/tuple/enum schema name that isn't
+    /// defined, or a custom or named-format validator name that isn't
+    /// registered.
+    InvalidReference,
+    /// A field is present but its `FieldCondition` guard isn't satisfied.
+    GuardViolation,
+    /// A `Constraint::Custom` validator rejected the value.
+    CustomValidation,
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::MissingRequiredField => "missing_required_field",
+            Self::TypeMismatch => "type_mismatch",
+            Self::FormatInvalid => "format_invalid",
+            Self::UnknownField => "unknown_field",
+            Self::ConstraintViolation => "constraint_violation",
+            Self::InvalidReference => "invalid_reference",
+            Self::GuardViolation => "guard_violation",
+            Self::CustomValidation => "custom_validation",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// How serious a [`ValidationDiagnostic`] is. Every error produced by
+/// [`Schema::validate`]/[`Schema::check`] today is [`Severity::Error`];
+/// the level exists so a future relaxed-validation mode (or a custom
+/// validator) can report [`Severity::Warning`] findings through the same
+/// type without inventing a parallel "warnings" list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
 /// Validation error details
 #[derive(Debug, Clone)]
-pub struct ValidationError {
-    pub path: String,
+pub struct ValidationDiagnostic {
+    pub path: JsonPath,
+    pub code: ErrorCode,
     pub message: String,
     pub expected_type: Option<String>,
     pub actual_type: Option<String>,
+    /// Byte offset range `(start, end)` of the offending value in the
+    /// source text, if known. `Schema::validate`/`Schema::check` don't
+    /// populate this today - `CfgppValue` doesn't retain source spans from
+    /// parsing - so it's always `None` coming out of this crate's own
+    /// validation. It exists so a caller with its own span information
+    /// (e.g. from walking the parser's tokens alongside the parsed value)
+    /// can attach one and still get a rendered snippet from [`Self::render`].
+    pub span: Option<(usize, usize)>,
+    pub severity: Severity,
+}
+
+/// How a single schema difference found by [`Schema::check_compatibility`]
+/// affects reader/writer interoperability, following Avro's schema
+/// resolution model: a "reader" on one schema version consuming data
+/// written by a "writer" on the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompatibilityKind {
+    /// Readers and writers on either schema version can interoperate freely.
+    Compatible,
+    /// A reader on the newer (`self`) schema can read data from the older
+    /// (`writer`) schema, but not the other way around.
+    BackwardOnly,
+    /// A reader on the older (`writer`) schema can read data from the newer
+    /// (`self`) schema, but not the other way around.
+    ForwardOnly,
+    /// Neither direction is safe.
+    Breaking,
+}
+
+/// A single classified difference between a reader and writer schema
+/// version, as found by [`Schema::check_compatibility`].
+#[derive(Debug, Clone)]
+pub struct CompatibilityChange {
+    pub path: String,
+    pub message: String,
+    pub kind: CompatibilityKind,
+}
+
+/// Result of [`Schema::check_compatibility`]: every classified difference
+/// between a reader schema and a writer schema version.
+#[derive(Debug, Clone, Default)]
+pub struct CompatibilityReport {
+    pub changes: Vec<CompatibilityChange>,
+}
+
+impl CompatibilityReport {
+    /// True if every change is at least backward-compatible, i.e. a reader
+    /// on the newer schema can still read data from the older one.
+    pub fn is_backward_compatible(&self) -> bool {
+        self.changes
+            .iter()
+            .all(|change| matches!(change.kind, CompatibilityKind::Compatible | CompatibilityKind::BackwardOnly))
+    }
+
+    /// True if every change is at least forward-compatible, i.e. a reader
+    /// on the older schema can still read data from the newer one.
+    pub fn is_forward_compatible(&self) -> bool {
+        self.changes
+            .iter()
+            .all(|change| matches!(change.kind, CompatibilityKind::Compatible | CompatibilityKind::ForwardOnly))
+    }
+
+    /// True if any change is breaking in both directions.
+    pub fn has_breaking_changes(&self) -> bool {
+        self.changes.iter().any(|change| change.kind == CompatibilityKind::Breaking)
+    }
 }
 
 impl Schema {
@@ -82,10 +577,29 @@ impl Schema {
             type_defs: HashMap::new(),
             enum_defs: HashMap::new(),
             object_schemas: HashMap::new(),
+            tuple_schemas: HashMap::new(),
             root_schema: None,
+            validators: ValidatorRegistry::new(),
+            format_validators: FormatValidatorRegistry::with_builtins(),
         }
     }
 
+    /// Register a named format validator, invoked whenever a field carries a
+    /// matching `Constraint::NamedFormat(name)`. Overrides the built-in
+    /// `"cpf"`/`"cnpj"` validators if `name` collides with one of them.
+    pub fn register_format_validator(&mut self, name: impl Into<String>, validator: impl FormatValidator + 'static) {
+        self.format_validators.register(name, validator);
+    }
+
+    /// Register a custom validator under `name`, invoked whenever a field
+    /// carries a matching `Constraint::Custom(name)`.
+    pub fn register_validator<F>(&mut self, name: impl Into<String>, validator: F)
+    where
+        F: Fn(&CfgppValue, &ValidationContext) -> Result<(), String> + 'static,
+    {
+        self.validators.register(name, validator);
+    }
+
     /// Parse schema from CFG++ schema definition string
     pub fn parse(schema_text: &str) -> CfgppResult<Self> {
         let mut schema = Self::new();
@@ -142,22 +656,68 @@ impl Schema {
         self.object_schemas.insert(name, fields);
     }
 
+    /// Add a tuple schema definition: `slots[i]` describes the value
+    /// expected at index `i` of an array validated against
+    /// `TypeDefinition::Tuple(name)`. A trailing run of non-required slots
+    /// may be omitted from the array entirely.
+    pub fn add_tuple_schema(&mut self, name: String, slots: Vec<FieldDefinition>) {
+        self.tuple_schemas.insert(name, slots);
+    }
+
     /// Set the root schema type
     pub fn set_root_schema(&mut self, type_def: TypeDefinition) {
         self.root_schema = Some(type_def);
     }
 
-    /// Validate a CFG++ value against this schema
-    pub fn validate(&self, value: &CfgppValue) -> Result<(), Vec<ValidationError>> {
-        let mut errors = Vec::new();
-        
+    /// The root type definition set via [`Schema::set_root_schema`], for
+    /// callers that need to walk the schema's shape directly instead of
+    /// going through [`Schema::validate`] - e.g.
+    /// [`crate::ast::AstNode::check_types`]'s static type-checking pass,
+    /// which walks an unevaluated AST alongside the schema rather than a
+    /// materialized [`CfgppValue`].
+    pub fn root_type(&self) -> Option<&TypeDefinition> {
+        self.root_schema.as_ref()
+    }
+
+    /// Field definitions for a named object schema added via
+    /// [`Schema::add_object_schema`].
+    pub fn object_fields(&self, name: &str) -> Option<&HashMap<String, FieldDefinition>> {
+        self.object_schemas.get(name)
+    }
+
+    /// Validate a CFG++ value against this schema, collecting every
+    /// diagnostic - both [`Severity::Error`] and [`Severity::Warning`] -
+    /// instead of stopping at the first one. Useful for editor integrations
+    /// that want to underline every problem in one pass rather than fixing
+    /// errors one at a time. [`Schema::validate`] is a convenience wrapper
+    /// around this that only fails on [`Severity::Error`] diagnostics.
+    pub fn validate_all(&self, value: &CfgppValue) -> Vec<ValidationDiagnostic> {
+        let mut diagnostics = Vec::new();
+        let root_path = JsonPath::root();
+
         if let Some(ref root_schema) = self.root_schema {
-            self.validate_type(value, root_schema, "", &mut errors);
+            self.validate_type(value, root_schema, &root_path, value, &mut diagnostics);
         } else {
             // If no root schema, try to infer validation based on value structure
-            self.validate_inferred(value, "", &mut errors);
+            self.validate_inferred(value, &root_path, &mut diagnostics);
         }
-        
+
+        diagnostics
+    }
+
+    /// Validate a CFG++ value against this schema. Fails only on diagnostics
+    /// with [`Severity::Error`] - a [`Severity::Warning`] doesn't fail
+    /// validation, but is still visible to callers that use
+    /// [`Schema::validate_all`] directly. The built-in checks, unknown
+    /// fields included, report only [`Severity::Error`]; an unknown field is
+    /// never downgraded to a warning.
+    pub fn validate(&self, value: &CfgppValue) -> Result<(), Vec<ValidationDiagnostic>> {
+        let errors: Vec<_> = self
+            .validate_all(value)
+            .into_iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+            .collect();
+
         if errors.is_empty() {
             Ok(())
         } else {
@@ -165,16 +725,19 @@ impl Schema {
         }
     }
 
-    /// Validate a specific field
-    pub fn validate_field(&self, value: &CfgppValue, field_def: &FieldDefinition, path: &str) -> Result<(), Vec<ValidationError>> {
+    /// Validate a specific field. `root` is the top-level value a custom
+    /// validator's [`ValidationContext`] can use for cross-field checks;
+    /// pass `value` itself if this field isn't part of a larger document.
+    pub fn validate_field(&self, value: &CfgppValue, field_def: &FieldDefinition, path: &str, root: &CfgppValue) -> Result<(), Vec<ValidationDiagnostic>> {
         let mut errors = Vec::new();
-        self.validate_type(value, &field_def.field_type, path, &mut errors);
-        
+        let json_path = JsonPath::opaque(path);
+        self.validate_type(value, &field_def.field_type, &json_path, root, &mut errors);
+
         // Apply constraints
         for constraint in &field_def.constraints {
-            self.validate_constraint(value, constraint, path, &mut errors);
+            self.validate_constraint(value, constraint, &json_path, root, &mut errors);
         }
-        
+
         if errors.is_empty() {
             Ok(())
         } else {
@@ -182,243 +745,481 @@ impl Schema {
         }
     }
 
-    fn validate_type(&self, value: &CfgppValue, type_def: &TypeDefinition, path: &str, errors: &mut Vec<ValidationError>) {
+    fn validate_type(&self, value: &CfgppValue, type_def: &TypeDefinition, path: &JsonPath, root: &CfgppValue, errors: &mut Vec<ValidationDiagnostic>) {
         match (value, type_def) {
             (CfgppValue::Null, TypeDefinition::Null) => {}
             (CfgppValue::Boolean(_), TypeDefinition::Boolean) => {}
             (CfgppValue::Integer(_), TypeDefinition::Integer) => {}
             (CfgppValue::Double(_), TypeDefinition::Double) => {}
             (CfgppValue::String(_), TypeDefinition::String) => {}
-            
+
             (CfgppValue::Array(arr), TypeDefinition::Array(element_type)) => {
                 for (i, element) in arr.iter().enumerate() {
-                    let element_path = format!("{}[{}]", path, i);
-                    self.validate_type(element, element_type, &element_path, errors);
+                    self.validate_type(element, element_type, &path.index(i), root, errors);
                 }
             }
-            
+
+            (CfgppValue::Array(arr), TypeDefinition::Tuple(schema_name)) => {
+                if let Some(slots) = self.tuple_schemas.get(schema_name) {
+                    for (i, slot) in slots.iter().enumerate() {
+                        let slot_path = path.index(i);
+                        match arr.get(i) {
+                            Some(element) => {
+                                self.validate_type(element, &slot.field_type, &slot_path, root, errors);
+                                for constraint in &slot.constraints {
+                                    self.validate_constraint(element, constraint, &slot_path, root, errors);
+                                }
+                            }
+                            None if slot.required => {
+                                errors.push(ValidationDiagnostic {
+                                    path: slot_path,
+                                    code: ErrorCode::MissingRequiredField,
+                                    message: format!("Missing field at index {}", i),
+                                    expected_type: Some(format!("{:?}", slot.field_type)),
+                                    actual_type: None,
+                                    span: None,
+                                    severity: Severity::Error,
+                                });
+                            }
+                            None => {}
+                        }
+                    }
+
+                    for (i, element) in arr.iter().enumerate().skip(slots.len()) {
+                        errors.push(ValidationDiagnostic {
+                            path: path.index(i),
+                            code: ErrorCode::UnknownField,
+                            message: format!("Struct has no field at index {}", i),
+                            expected_type: None,
+                            actual_type: Some(element.type_name().to_string()),
+                            span: None,
+                            severity: Severity::Error,
+                        });
+                    }
+                } else {
+                    errors.push(ValidationDiagnostic {
+                        path: path.clone(),
+                        code: ErrorCode::InvalidReference,
+                        message: format!("Unknown tuple schema '{}'", schema_name),
+                        expected_type: Some(format!("tuple({})", schema_name)),
+                        actual_type: Some(value.type_name().to_string()),
+                        span: None,
+                        severity: Severity::Error,
+                    });
+                }
+            }
+
             (CfgppValue::Object(obj), TypeDefinition::Object(schema_name)) => {
                 if let Some(schema_fields) = self.object_schemas.get(schema_name) {
                     // Check required fields
                     for (field_name, field_def) in schema_fields {
-                        let field_path = if path.is_empty() {
-                            field_name.clone()
-                        } else {
-                            format!("{}.{}", path, field_name)
-                        };
-                        
-                        if let Some(field_value) = obj.get(field_name) {
-                            self.validate_type(field_value, &field_def.field_type, &field_path, errors);
-                            
-                            // Apply field constraints
-                            for constraint in &field_def.constraints {
-                                self.validate_constraint(field_value, constraint, &field_path, errors);
+                        let field_path = path.field(field_name);
+
+                        let guard_satisfied = field_def
+                            .guard
+                            .as_ref()
+                            .map(|condition| Self::eval_condition(condition, obj))
+                            .unwrap_or(true);
+
+                        match (obj.get(field_name), guard_satisfied) {
+                            (Some(field_value), true) => {
+                                self.validate_type(field_value, &field_def.field_type, &field_path, root, errors);
+
+                                // Apply field constraints
+                                for constraint in &field_def.constraints {
+                                    self.validate_constraint(field_value, constraint, &field_path, root, errors);
+                                }
                             }
-                        } else if field_def.required {
-                            errors.push(ValidationError {
-                                path: field_path,
-                                message: format!("Required field '{}' is missing", field_name),
-                                expected_type: Some(format!("{:?}", field_def.field_type)),
-                                actual_type: None,
-                            });
+                            (Some(_), false) => {
+                                errors.push(ValidationDiagnostic {
+                                    path: field_path,
+                                    code: ErrorCode::GuardViolation,
+                                    message: format!("Field '{}' is present but its guard condition is not satisfied", field_name),
+                                    expected_type: None,
+                                    actual_type: None,
+                                    span: None,
+                                    severity: Severity::Error,
+                                });
+                            }
+                            (None, true) if field_def.required => {
+                                errors.push(ValidationDiagnostic {
+                                    path: field_path,
+                                    code: ErrorCode::MissingRequiredField,
+                                    message: format!("Required field '{}' is missing", field_name),
+                                    expected_type: Some(format!("{:?}", field_def.field_type)),
+                                    actual_type: None,
+                                    span: None,
+                                    severity: Severity::Error,
+                                });
+                            }
+                            (None, _) => {}
                         }
                     }
-                    
+
                     // Check for unexpected fields
                     for field_name in obj.keys() {
                         if !schema_fields.contains_key(field_name) {
-                            let field_path = if path.is_empty() {
-                                field_name.clone()
-                            } else {
-                                format!("{}.{}", path, field_name)
-                            };
-                            errors.push(ValidationError {
-                                path: field_path,
+                            errors.push(ValidationDiagnostic {
+                                path: path.field(field_name),
+                                code: ErrorCode::UnknownField,
                                 message: format!("Unexpected field '{}'", field_name),
                                 expected_type: None,
                                 actual_type: Some(obj.get(field_name).unwrap().type_name().to_string()),
+                                span: None,
+                                severity: Severity::Error,
                             });
                         }
                     }
                 } else {
-                    errors.push(ValidationError {
-                        path: path.to_string(),
+                    errors.push(ValidationDiagnostic {
+                        path: path.clone(),
+                        code: ErrorCode::InvalidReference,
                         message: format!("Unknown object schema '{}'", schema_name),
                         expected_type: Some(format!("object({})", schema_name)),
                         actual_type: Some(value.type_name().to_string()),
+                        span: None,
+                        severity: Severity::Error,
                     });
                 }
             }
-            
+
             (CfgppValue::Enum(enum_value), TypeDefinition::Enum(enum_name)) => {
                 if let Some(valid_values) = self.enum_defs.get(enum_name) {
                     if !valid_values.contains(enum_value) {
-                        errors.push(ValidationError {
-                            path: path.to_string(),
+                        errors.push(ValidationDiagnostic {
+                            path: path.clone(),
+                            code: ErrorCode::TypeMismatch,
                             message: format!("Invalid enum value '{}', expected one of: {}", enum_value, valid_values.join(", ")),
                             expected_type: Some(format!("enum({})", enum_name)),
                             actual_type: Some(format!("enum({})", enum_value)),
+                            span: None,
+                            severity: Severity::Error,
                         });
                     }
                 } else {
-                    errors.push(ValidationError {
-                        path: path.to_string(),
+                    errors.push(ValidationDiagnostic {
+                        path: path.clone(),
+                        code: ErrorCode::InvalidReference,
                         message: format!("Unknown enum type '{}'", enum_name),
                         expected_type: Some(format!("enum({})", enum_name)),
                         actual_type: Some(value.type_name().to_string()),
+                        span: None,
+                        severity: Severity::Error,
                     });
                 }
             }
-            
+
             (value, TypeDefinition::Union(types)) => {
                 let mut union_errors = Vec::new();
                 let mut matched = false;
-                
+
                 for union_type in types {
                     let mut type_errors = Vec::new();
-                    self.validate_type(value, union_type, path, &mut type_errors);
+                    self.validate_type(value, union_type, path, root, &mut type_errors);
                     if type_errors.is_empty() {
                         matched = true;
                         break;
                     }
                     union_errors.extend(type_errors);
                 }
-                
+
                 if !matched {
-                    errors.push(ValidationError {
-                        path: path.to_string(),
+                    errors.push(ValidationDiagnostic {
+                        path: path.clone(),
+                        code: ErrorCode::TypeMismatch,
                         message: format!("Value does not match any type in union: {:?}", types),
                         expected_type: Some(format!("union({:?})", types)),
                         actual_type: Some(value.type_name().to_string()),
+                        span: None,
+                        severity: Severity::Error,
                     });
                 }
             }
-            
+
+            (value, TypeDefinition::OneOf(types)) => {
+                let matched_indices: Vec<usize> = types
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, candidate_type)| {
+                        let mut candidate_errors = Vec::new();
+                        self.validate_type(value, candidate_type, path, root, &mut candidate_errors);
+                        candidate_errors.is_empty()
+                    })
+                    .map(|(i, _)| i)
+                    .collect();
+
+                if matched_indices.is_empty() {
+                    errors.push(ValidationDiagnostic {
+                        path: path.clone(),
+                        code: ErrorCode::TypeMismatch,
+                        message: format!("Value does not match any branch of oneOf: {:?}", types),
+                        expected_type: Some(format!("oneOf({:?})", types)),
+                        actual_type: Some(value.type_name().to_string()),
+                        span: None,
+                        severity: Severity::Error,
+                    });
+                } else if matched_indices.len() > 1 {
+                    errors.push(ValidationDiagnostic {
+                        path: path.clone(),
+                        code: ErrorCode::TypeMismatch,
+                        message: format!(
+                            "Value matches {} branches of oneOf (indices {:?}), but exactly one must match",
+                            matched_indices.len(), matched_indices
+                        ),
+                        expected_type: Some(format!("oneOf({:?})", types)),
+                        actual_type: Some(value.type_name().to_string()),
+                        span: None,
+                        severity: Severity::Error,
+                    });
+                }
+            }
+
             (value, TypeDefinition::Optional(inner_type)) => {
                 if !value.is_null() {
-                    self.validate_type(value, inner_type, path, errors);
+                    self.validate_type(value, inner_type, path, root, errors);
                 }
             }
-            
+
             _ => {
-                errors.push(ValidationError {
-                    path: path.to_string(),
+                errors.push(ValidationDiagnostic {
+                    path: path.clone(),
+                    code: ErrorCode::TypeMismatch,
                     message: format!("Type mismatch"),
                     expected_type: Some(format!("{:?}", type_def)),
                     actual_type: Some(value.type_name().to_string()),
+                    span: None,
+                    severity: Severity::Error,
                 });
             }
         }
     }
 
-    fn validate_constraint(&self, value: &CfgppValue, constraint: &Constraint, path: &str, errors: &mut Vec<ValidationError>) {
+    /// Evaluate a [`FieldCondition`] against the object a guarded field
+    /// belongs to. A `field_path` that traverses a missing intermediate
+    /// object short-circuits to `false` rather than erroring, since "the
+    /// guard field isn't there" is itself meaningful input (e.g. `Exists`
+    /// rightly reports false, and `Eq` can't hold against nothing).
+    fn eval_condition(condition: &FieldCondition, obj: &CfgppObject) -> bool {
+        match condition {
+            FieldCondition::Eq(field_path, expected) => {
+                Self::resolve_field_path(obj, field_path) == Some(expected)
+            }
+            FieldCondition::Exists(field_path) => {
+                Self::resolve_field_path(obj, field_path).is_some()
+            }
+            FieldCondition::And(lhs, rhs) => {
+                Self::eval_condition(lhs, obj) && Self::eval_condition(rhs, obj)
+            }
+            FieldCondition::Or(lhs, rhs) => {
+                Self::eval_condition(lhs, obj) || Self::eval_condition(rhs, obj)
+            }
+            FieldCondition::Not(inner) => !Self::eval_condition(inner, obj),
+        }
+    }
+
+    /// Resolve a dotted `field_path` (e.g. `"tls.enabled"`) relative to
+    /// `obj`, the object currently being validated.
+    fn resolve_field_path<'a>(obj: &'a CfgppObject, field_path: &str) -> Option<&'a CfgppValue> {
+        let (first, rest) = match field_path.split_once('.') {
+            Some((first, rest)) => (first, Some(rest)),
+            None => (field_path, None),
+        };
+        let value = obj.get(first)?;
+        match rest {
+            Some(rest) => value.get_path(rest),
+            None => Some(value),
+        }
+    }
+
+    fn validate_constraint(&self, value: &CfgppValue, constraint: &Constraint, path: &JsonPath, root: &CfgppValue, errors: &mut Vec<ValidationDiagnostic>) {
         match constraint {
             Constraint::MinLength(min_len) => {
                 if let Some(s) = value.as_string() {
                     if s.len() < *min_len {
-                        errors.push(ValidationError {
-                            path: path.to_string(),
+                        errors.push(ValidationDiagnostic {
+                            path: path.clone(),
+                            code: ErrorCode::ConstraintViolation,
                             message: format!("String length {} is less than minimum {}", s.len(), min_len),
                             expected_type: None,
                             actual_type: None,
+                            span: None,
+                            severity: Severity::Error,
                         });
                     }
                 }
             }
-            
+
             Constraint::MaxLength(max_len) => {
                 if let Some(s) = value.as_string() {
                     if s.len() > *max_len {
-                        errors.push(ValidationError {
-                            path: path.to_string(),
+                        errors.push(ValidationDiagnostic {
+                            path: path.clone(),
+                            code: ErrorCode::ConstraintViolation,
                             message: format!("String length {} exceeds maximum {}", s.len(), max_len),
                             expected_type: None,
                             actual_type: None,
+                            span: None,
+                            severity: Severity::Error,
                         });
                     }
                 }
             }
-            
+
             Constraint::MinValue(min_val) => {
                 let num_val = match value {
                     CfgppValue::Integer(i) => Some(*i as f64),
                     CfgppValue::Double(d) => Some(*d),
                     _ => None,
                 };
-                
+
                 if let Some(val) = num_val {
                     if val < *min_val {
-                        errors.push(ValidationError {
-                            path: path.to_string(),
+                        errors.push(ValidationDiagnostic {
+                            path: path.clone(),
+                            code: ErrorCode::ConstraintViolation,
                             message: format!("Value {} is less than minimum {}", val, min_val),
                             expected_type: None,
                             actual_type: None,
+                            span: None,
+                            severity: Severity::Error,
                         });
                     }
                 }
             }
-            
+
             Constraint::MaxValue(max_val) => {
                 let num_val = match value {
                     CfgppValue::Integer(i) => Some(*i as f64),
                     CfgppValue::Double(d) => Some(*d),
                     _ => None,
                 };
-                
+
                 if let Some(val) = num_val {
                     if val > *max_val {
-                        errors.push(ValidationError {
-                            path: path.to_string(),
+                        errors.push(ValidationDiagnostic {
+                            path: path.clone(),
+                            code: ErrorCode::ConstraintViolation,
                             message: format!("Value {} exceeds maximum {}", val, max_val),
                             expected_type: None,
                             actual_type: None,
+                            span: None,
+                            severity: Severity::Error,
                         });
                     }
                 }
             }
-            
+
             Constraint::Pattern(regex) => {
                 if let Some(s) = value.as_string() {
                     if !regex.is_match(s) {
-                        errors.push(ValidationError {
-                            path: path.to_string(),
+                        errors.push(ValidationDiagnostic {
+                            path: path.clone(),
+                            code: ErrorCode::ConstraintViolation,
                             message: format!("String '{}' does not match pattern {}", s, regex.as_str()),
                             expected_type: None,
                             actual_type: None,
+                            span: None,
+                            severity: Severity::Error,
                         });
                     }
                 }
             }
-            
-            Constraint::Custom(_function_name) => {
-                // Custom validation would be implemented by the user
-                // For now, we just skip it
-            }
-        }
-    }
 
-    fn validate_inferred(&self, value: &CfgppValue, path: &str, errors: &mut Vec<ValidationError>) {
-        // Basic validation without explicit schema
-        match value {
-            CfgppValue::Object(obj) => {
-                for (key, val) in obj {
-                    let field_path = if path.is_empty() {
-                        key.clone()
-                    } else {
-                        format!("{}.{}", path, key)
-                    };
-                    self.validate_inferred(val, &field_path, errors);
-                }
-            }
-            CfgppValue::Array(arr) => {
-                for (i, element) in arr.iter().enumerate() {
-                    let element_path = format!("{}[{}]", path, i);
-                    self.validate_inferred(element, &element_path, errors);
+            Constraint::Format(kind) => {
+                if let Some(s) = value.as_string() {
+                    if !kind.matches(s) {
+                        errors.push(ValidationDiagnostic {
+                            path: path.clone(),
+                            code: ErrorCode::FormatInvalid,
+                            message: format!("String '{}' does not match format '{}'", s, kind),
+                            expected_type: None,
+                            actual_type: None,
+                            span: None,
+                            severity: Severity::Error,
+                        });
+                    }
                 }
             }
-            _ => {
-                // Basic value types are always valid
-            }
+
+            Constraint::NamedFormat(name) => {
+                if let Some(s) = value.as_string() {
+                    match self.format_validators.get(name) {
+                        Some(validator) => {
+                            if let Err(message) = validator.validate(s) {
+                                errors.push(ValidationDiagnostic {
+                                    path: path.clone(),
+                                    code: ErrorCode::FormatInvalid,
+                                    message: format!("String '{}' does not match format '{}': {}", s, name, message),
+                                    expected_type: None,
+                                    actual_type: None,
+                                    span: None,
+                                    severity: Severity::Error,
+                                });
+                            }
+                        }
+                        None => {
+                            errors.push(ValidationDiagnostic {
+                                path: path.clone(),
+                                code: ErrorCode::InvalidReference,
+                                message: format!("No format validator registered for '{}'", name),
+                                expected_type: None,
+                                actual_type: None,
+                                span: None,
+                                severity: Severity::Error,
+                            });
+                        }
+                    }
+                }
+            }
+
+            Constraint::Custom(name) => {
+                match self.validators.get(name) {
+                    Some(validator) => {
+                        let context = ValidationContext { path: path.as_str(), root };
+                        if let Err(message) = validator(value, &context) {
+                            errors.push(ValidationDiagnostic {
+                                path: path.clone(),
+                                code: ErrorCode::CustomValidation,
+                                message,
+                                expected_type: None,
+                                actual_type: None,
+                                span: None,
+                                severity: Severity::Error,
+                            });
+                        }
+                    }
+                    None => {
+                        errors.push(ValidationDiagnostic {
+                            path: path.clone(),
+                            code: ErrorCode::InvalidReference,
+                            message: format!("No custom validator registered for '{}'", name),
+                            expected_type: None,
+                            actual_type: None,
+                            span: None,
+                            severity: Severity::Error,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate_inferred(&self, value: &CfgppValue, path: &JsonPath, errors: &mut Vec<ValidationDiagnostic>) {
+        // Basic validation without explicit schema
+        match value {
+            CfgppValue::Object(obj) => {
+                for (key, val) in obj {
+                    self.validate_inferred(val, &path.field(key), errors);
+                }
+            }
+            CfgppValue::Array(arr) => {
+                for (i, element) in arr.iter().enumerate() {
+                    self.validate_inferred(element, &path.index(i), errors);
+                }
+            }
+            _ => {
+                // Basic value types are always valid
+            }
         }
     }
 
@@ -506,6 +1307,8 @@ impl Schema {
                     required: true, // Default to required
                     default_value: None,
                     constraints: Vec::new(),
+                    aliases: Vec::new(),
+                    guard: None,
                 };
                 
                 fields.insert(field_name, field_def);
@@ -517,144 +1320,3237 @@ impl Schema {
         Ok(((object_name, fields), line_count + 1))
     }
 
-    fn parse_type_definition(type_str: &str) -> CfgppResult<TypeDefinition> {
-        let trimmed = type_str.trim();
-        
-        match trimmed {
-            "null" => Ok(TypeDefinition::Null),
-            "boolean" => Ok(TypeDefinition::Boolean),
-            "integer" => Ok(TypeDefinition::Integer),
-            "double" => Ok(TypeDefinition::Double),
-            "string" => Ok(TypeDefinition::String),
-            _ => {
-                // Handle array types like "array<string>"
-                if trimmed.starts_with("array<") && trimmed.ends_with('>') {
-                    let inner_type = &trimmed[6..trimmed.len() - 1];
-                    let element_type = Self::parse_type_definition(inner_type)?;
-                    return Ok(TypeDefinition::Array(Box::new(element_type)));
-                }
-                
-                // Handle optional types like "optional<string>"
-                if trimmed.starts_with("optional<") && trimmed.ends_with('>') {
-                    let inner_type = &trimmed[9..trimmed.len() - 1];
-                    let element_type = Self::parse_type_definition(inner_type)?;
-                    return Ok(TypeDefinition::Optional(Box::new(element_type)));
+    /// Check that this schema is internally consistent, independent of any
+    /// value being validated against it: unresolved object/enum references,
+    /// duplicate or empty enums, constraints attached to an incompatible
+    /// field type, and required-only reference cycles that no finite value
+    /// could ever satisfy. `Schema::parse` and `parse_type_definition` don't
+    /// themselves reject a dangling reference - they only surface as a
+    /// validation error the first time `validate()` runs against data - so
+    /// call this right after building or parsing a schema to catch mistakes
+    /// up front instead.
+    pub fn check(&self) -> Vec<ValidationDiagnostic> {
+        let mut errors = Vec::new();
+
+        for (enum_name, values) in &self.enum_defs {
+            Self::check_enum_def(enum_name, values, &mut errors);
+        }
+
+        for (object_name, fields) in &self.object_schemas {
+            for (field_name, field_def) in fields {
+                let path = format!("{}.{}", object_name, field_name);
+                self.check_type_def(&field_def.field_type, &path, &mut errors);
+                self.check_constraints(field_def, &path, &mut errors);
+            }
+        }
+
+        for (tuple_name, slots) in &self.tuple_schemas {
+            for (i, slot) in slots.iter().enumerate() {
+                let path = format!("{}[{}]", tuple_name, i);
+                self.check_type_def(&slot.field_type, &path, &mut errors);
+                self.check_constraints(slot, &path, &mut errors);
+            }
+        }
+
+        for type_def in self.type_defs.values() {
+            self.check_type_def(type_def, "<type_defs>", &mut errors);
+        }
+
+        if let Some(root) = &self.root_schema {
+            self.check_type_def(root, "<root>", &mut errors);
+        }
+
+        self.check_required_cycles(&mut errors);
+
+        errors
+    }
+
+    /// Compare `self` (the newer, "reader" schema) against `writer` (an
+    /// older schema version data may already have been written against),
+    /// following Avro's reader/writer resolution rules. Object schemas and
+    /// enums present on only one side are reported as new/removed by name;
+    /// those present on both are walked field-by-field, matching fields by
+    /// name or [`FieldDefinition::aliases`].
+    ///
+    /// This models the classic Avro resolution rules directly rather than
+    /// replaying `validate()`'s own stricter "unexpected field" check: a
+    /// reader ignores a field it doesn't recognize instead of rejecting it,
+    /// so adding or removing a field is compatible in whichever direction
+    /// only depends on whether the *other* side's schema can tolerate the
+    /// field's absence - not flatly `Breaking` just because the field
+    /// existed on one side and not the other. A
+    /// `Compatible`/`BackwardOnly`/`ForwardOnly` verdict here is about
+    /// whether the *data* two schema versions produce can still be resolved
+    /// against each other, not whether this crate's `validate()` happens to
+    /// accept it unmodified. Likewise, type/constraint widening and
+    /// narrowing are classified directionally (`BackwardOnly`/`ForwardOnly`),
+    /// not as a flat `Compatible`/`Breaking` - a reader only actually
+    /// promotes data coming from the *narrower* side of a widened pair, so
+    /// the other direction still depends on what the data happens to be.
+    pub fn check_compatibility(&self, writer: &Schema) -> CompatibilityReport {
+        let mut changes = Vec::new();
+
+        self.check_enum_compatibility(writer, &mut changes);
+
+        for (object_name, reader_fields) in &self.object_schemas {
+            let Some(writer_fields) = writer.object_schemas.get(object_name) else {
+                changes.push(CompatibilityChange {
+                    path: format!("object {}", object_name),
+                    message: format!(
+                        "object schema '{}' is new; any field that referenced it by this name under the writer schema would not have resolved",
+                        object_name
+                    ),
+                    kind: CompatibilityKind::Compatible,
+                });
+                continue;
+            };
+
+            for (field_name, reader_field, writer_field) in Self::match_fields(reader_fields, writer_fields) {
+                let path = format!("{}.{}", object_name, field_name);
+                Self::classify_field(&path, reader_field, writer_field, &mut changes);
+            }
+        }
+
+        for object_name in writer.object_schemas.keys() {
+            if !self.object_schemas.contains_key(object_name) {
+                changes.push(CompatibilityChange {
+                    path: format!("object {}", object_name),
+                    message: format!(
+                        "object schema '{}' was removed; any field still referencing it by this name under the writer schema can no longer resolve",
+                        object_name
+                    ),
+                    kind: CompatibilityKind::Breaking,
+                });
+            }
+        }
+
+        CompatibilityReport { changes }
+    }
+
+    /// A hash of this schema's normalized structure: every type, enum,
+    /// object, and tuple schema definition, with map keys sorted so that two `Schema`s
+    /// built from the same definitions in a different order (or read back
+    /// from a `HashMap` with different iteration order) still fingerprint
+    /// identically. Two schemas with equal fingerprints are structurally
+    /// interchangeable for validation purposes; unequal fingerprints mean
+    /// *something* differs, though not necessarily how - use
+    /// [`Self::check_compatibility`] to find out what and whether it matters.
+    ///
+    /// This hashes a formatted string rather than deriving [`std::hash::Hash`]
+    /// because [`Constraint::Pattern`] wraps a [`Regex`], which doesn't
+    /// implement it.
+    pub fn fingerprint(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.canonical_form().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Builds the normalized string [`Self::fingerprint`] hashes: one line
+    /// per type/enum/object/tuple definition, each sorted by name so ordering
+    /// never affects the result.
+    fn canonical_form(&self) -> String {
+        let mut out = String::new();
+
+        let mut type_names: Vec<&String> = self.type_defs.keys().collect();
+        type_names.sort();
+        for name in type_names {
+            out.push_str(&format!("type {}={}\n", name, Self::canonical_type(&self.type_defs[name])));
+        }
+
+        let mut enum_names: Vec<&String> = self.enum_defs.keys().collect();
+        enum_names.sort();
+        for name in enum_names {
+            let mut values = self.enum_defs[name].clone();
+            values.sort();
+            out.push_str(&format!("enum {}={}\n", name, Self::encode_list(values.iter().map(String::as_str))));
+        }
+
+        let mut object_names: Vec<&String> = self.object_schemas.keys().collect();
+        object_names.sort();
+        for object_name in object_names {
+            out.push_str(&format!("object {}:\n", object_name));
+            let fields = &self.object_schemas[object_name];
+            let mut field_names: Vec<&String> = fields.keys().collect();
+            field_names.sort();
+            for field_name in field_names {
+                out.push_str(&format!("  {}={}\n", field_name, Self::canonical_field(&fields[field_name])));
+            }
+        }
+
+        let mut tuple_names: Vec<&String> = self.tuple_schemas.keys().collect();
+        tuple_names.sort();
+        for tuple_name in tuple_names {
+            let slots: Vec<String> = self.tuple_schemas[tuple_name].iter().map(Self::canonical_field).collect();
+            out.push_str(&format!("tuple {}={}\n", tuple_name, Self::encode_list(slots.iter().map(String::as_str))));
+        }
+
+        if let Some(root) = &self.root_schema {
+            out.push_str(&format!("root={}\n", Self::canonical_type(root)));
+        }
+
+        out
+    }
+
+    /// Joins `items` into a single string with each entry prefixed by its
+    /// byte length (`"<len>:<text>"`), so the boundaries between entries are
+    /// unambiguous no matter what characters the entries themselves contain
+    /// - a plain `","`-joined list would let e.g. enum values `"a"`/`"b,c"`
+    /// and `"a,b"`/`"c"` render identically, silently colliding two
+    /// different schemas onto the same [`Self::fingerprint`].
+    fn encode_list<'a>(items: impl Iterator<Item = &'a str>) -> String {
+        items.map(|item| format!("{}:{}", item.len(), item)).collect()
+    }
+
+    fn canonical_type(type_def: &TypeDefinition) -> String {
+        match type_def {
+            TypeDefinition::Null => "null".to_string(),
+            TypeDefinition::Boolean => "boolean".to_string(),
+            TypeDefinition::Integer => "integer".to_string(),
+            TypeDefinition::Double => "double".to_string(),
+            TypeDefinition::String => "string".to_string(),
+            TypeDefinition::Array(element) => format!("array<{}>", Self::canonical_type(element)),
+            TypeDefinition::Object(name) => format!("object<{}>", name),
+            TypeDefinition::Tuple(name) => format!("tuple<{}>", name),
+            TypeDefinition::Enum(name) => format!("enum<{}>", name),
+            TypeDefinition::Union(members) => {
+                let mut rendered: Vec<String> = members.iter().map(Self::canonical_type).collect();
+                rendered.sort();
+                format!("union<{}>", Self::encode_list(rendered.iter().map(String::as_str)))
+            }
+            TypeDefinition::OneOf(members) => {
+                let mut rendered: Vec<String> = members.iter().map(Self::canonical_type).collect();
+                rendered.sort();
+                format!("oneOf<{}>", Self::encode_list(rendered.iter().map(String::as_str)))
+            }
+            TypeDefinition::Optional(inner) => format!("optional<{}>", Self::canonical_type(inner)),
+        }
+    }
+
+    fn canonical_field(field: &FieldDefinition) -> String {
+        let mut aliases = field.aliases.clone();
+        aliases.sort();
+        let mut constraints: Vec<String> = field.constraints.iter().map(Self::canonical_constraint).collect();
+        constraints.sort();
+        let default = field
+            .default_value
+            .as_ref()
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| "none".to_string());
+        format!(
+            "type={} required={} default={} aliases=[{}] constraints=[{}] guard={:?}",
+            Self::canonical_type(&field.field_type),
+            field.required,
+            default,
+            Self::encode_list(aliases.iter().map(String::as_str)),
+            Self::encode_list(constraints.iter().map(String::as_str)),
+            field.guard
+        )
+    }
+
+    fn canonical_constraint(constraint: &Constraint) -> String {
+        match constraint {
+            Constraint::MinLength(v) => format!("min_length({})", v),
+            Constraint::MaxLength(v) => format!("max_length({})", v),
+            Constraint::MinValue(v) => format!("min_value({})", v),
+            Constraint::MaxValue(v) => format!("max_value({})", v),
+            Constraint::Pattern(re) => format!("pattern({})", re.as_str()),
+            Constraint::Format(kind) => format!("format({})", kind),
+            Constraint::NamedFormat(name) => format!("named_format({})", name),
+            Constraint::Custom(name) => format!("custom({})", name),
+        }
+    }
+
+    /// Pairs up every field across `reader_fields` and `writer_fields`,
+    /// keyed by name OR by either side's `aliases`, so a rename shows up as
+    /// one changed field rather than an unrelated add/remove pair. A field
+    /// present on only one side comes back with the other slot `None`.
+    fn match_fields<'a>(
+        reader_fields: &'a HashMap<String, FieldDefinition>,
+        writer_fields: &'a HashMap<String, FieldDefinition>,
+    ) -> Vec<(&'a str, Option<&'a FieldDefinition>, Option<&'a FieldDefinition>)> {
+        let writer_lookup = Self::field_name_lookup(writer_fields);
+
+        // Resolve every reader field's candidate writer name up front, then
+        // sort by reader name before claiming one. Two reader fields whose
+        // name/aliases both resolve to the same writer field (a copy-pasted
+        // alias, say) would otherwise both match it depending on arbitrary
+        // HashMap iteration order; sorting first makes the winner
+        // deterministic, and the loser falls back to being treated as a new
+        // field rather than silently double-matching.
+        let mut candidates: Vec<(&str, &FieldDefinition, Option<&str>)> = reader_fields
+            .iter()
+            .map(|(reader_name, reader_field)| {
+                let writer_name = std::iter::once(reader_name.as_str())
+                    .chain(reader_field.aliases.iter().map(String::as_str))
+                    .find_map(|candidate| writer_lookup.get(candidate).copied());
+                (reader_name.as_str(), reader_field, writer_name)
+            })
+            .collect();
+        candidates.sort_by_key(|(reader_name, _, _)| *reader_name);
+
+        let mut matched_writer_names = std::collections::HashSet::new();
+        let mut pairs = Vec::new();
+
+        for (reader_name, reader_field, writer_name) in candidates {
+            match writer_name {
+                Some(writer_name) if matched_writer_names.insert(writer_name) => {
+                    pairs.push((reader_name, Some(reader_field), writer_fields.get(writer_name)));
                 }
-                
-                // Assume it's a custom type (object or enum)
-                Ok(TypeDefinition::Object(trimmed.to_string()))
+                _ => pairs.push((reader_name, Some(reader_field), None)),
+            }
+        }
+
+        for (writer_name, writer_field) in writer_fields {
+            if !matched_writer_names.contains(writer_name.as_str()) {
+                pairs.push((writer_name.as_str(), None, Some(writer_field)));
             }
         }
+
+        pairs
     }
-}
 
-impl Default for Schema {
-    fn default() -> Self {
-        Self::new()
+    /// Maps every name a field in `fields` is known by - its key plus all of
+    /// its `aliases` - to that field's canonical (key) name.
+    /// Builds an alias/name -> canonical-field-name lookup. A field's own
+    /// name always wins its own slot: real names are registered in a first
+    /// pass, before any alias is considered, so a field whose name collides
+    /// with another field's alias (a copy-paste mistake in the schema, not
+    /// something this crate can prevent) still resolves to itself rather
+    /// than being shadowed. Aliases are then added in sorted-name order and
+    /// never overwrite an entry once set, so two fields that happen to
+    /// share an alias resolve deterministically to whichever field name
+    /// sorts first, rather than depending on `HashMap` iteration order.
+    fn field_name_lookup(fields: &HashMap<String, FieldDefinition>) -> HashMap<&str, &str> {
+        let mut lookup = HashMap::new();
+        for name in fields.keys() {
+            lookup.insert(name.as_str(), name.as_str());
+        }
+
+        let mut names: Vec<&String> = fields.keys().collect();
+        names.sort();
+        for name in names {
+            for alias in &fields[name].aliases {
+                lookup.entry(alias.as_str()).or_insert(name.as_str());
+            }
+        }
+        lookup
     }
-}
 
-impl FieldDefinition {
-    /// Create a new field definition
-    pub fn new(field_type: TypeDefinition, required: bool) -> Self {
-        Self {
-            field_type,
-            required,
-            default_value: None,
-            constraints: Vec::new(),
+    /// True if `field` can be absent from a document without losing
+    /// information: it isn't `required`, it carries a `default_value`, or
+    /// its type is itself `Optional`.
+    ///
+    /// Note this is the Avro-resolution model the request for this feature
+    /// describes, not a description of [`Schema::validate`]'s current
+    /// behavior - `validate` checks `required` alone when a field is absent
+    /// and doesn't substitute in `default_value` for it, so a
+    /// `required: true` field with a `default_value` is actually rejected as
+    /// missing today. `check_compatibility` answers "would this be
+    /// schema-evolution-safe," which is a question about the schemas'
+    /// declared intent, not a claim that `validate()` already honors it.
+    fn field_can_be_absent(field: &FieldDefinition) -> bool {
+        Self::field_explicitly_optional(field) || matches!(field.field_type, TypeDefinition::Optional(_))
+    }
+
+    /// Like [`Self::field_can_be_absent`], but ignores `Optional(_)` field
+    /// types. Used by [`Self::classify_field_requiredness`], which only
+    /// runs when a field exists on both sides - there, a transition into or
+    /// out of `Optional` is already reported by [`Self::classify_field_type`],
+    /// so counting it here too would raise two separate, conflicting
+    /// changes (one `ForwardOnly`/`BackwardOnly` from the type narrowing,
+    /// one `Breaking` from this check) for what is really a single change.
+    fn field_explicitly_optional(field: &FieldDefinition) -> bool {
+        !field.required || field.default_value.is_some()
+    }
+
+    fn classify_field(
+        path: &str,
+        reader: Option<&FieldDefinition>,
+        writer: Option<&FieldDefinition>,
+        changes: &mut Vec<CompatibilityChange>,
+    ) {
+        match (reader, writer) {
+            (Some(reader_field), None) => {
+                // A reader that doesn't recognize a field ignores it in the
+                // data it's handed - so an old reader on the writer schema
+                // reads data containing this new field just fine (forward
+                // always holds). Backward only holds if the new field can be
+                // absent: old data never had it, so the new reader schema
+                // needs to tolerate that absence itself.
+                let kind = if Self::field_can_be_absent(reader_field) {
+                    CompatibilityKind::Compatible
+                } else {
+                    CompatibilityKind::ForwardOnly
+                };
+                changes.push(CompatibilityChange {
+                    path: path.to_string(),
+                    message: format!(
+                        "field '{}' is new{}",
+                        path,
+                        if kind == CompatibilityKind::ForwardOnly {
+                            " and required with no default value; a reader on this schema can't resolve old data that predates the field, but a reader still on the writer schema simply ignores it when reading data this schema produces"
+                        } else {
+                            ", but is optional or has a default; old data missing it still reads fine"
+                        }
+                    ),
+                    kind,
+                });
+            }
+            (None, Some(writer_field)) => {
+                // Mirror of the new-field case: a reader built from this
+                // schema simply ignores the field in writer-schema data, so
+                // backward always holds. Forward only holds if the writer
+                // schema let the field be absent - otherwise a reader still
+                // on the writer schema requires it and won't find it in data
+                // this schema produces.
+                let kind = if Self::field_can_be_absent(writer_field) {
+                    CompatibilityKind::Compatible
+                } else {
+                    CompatibilityKind::BackwardOnly
+                };
+                changes.push(CompatibilityChange {
+                    path: path.to_string(),
+                    message: format!(
+                        "field '{}' was removed{}",
+                        path,
+                        if kind == CompatibilityKind::BackwardOnly {
+                            "; the writer schema required it with no default, so a reader still on the writer schema won't find it in data this schema produces"
+                        } else {
+                            ", but it was optional or had a default under the writer schema, so a reader still on the old schema tolerates its absence"
+                        }
+                    ),
+                    kind,
+                });
+            }
+            (Some(reader_field), Some(writer_field)) => {
+                Self::classify_field_type(path, &reader_field.field_type, &writer_field.field_type, changes);
+                Self::classify_field_constraints(path, reader_field, writer_field, changes);
+                Self::classify_field_requiredness(path, reader_field, writer_field, changes);
+            }
+            (None, None) => {}
         }
     }
-    
-    /// Add a constraint to this field
-    pub fn with_constraint(mut self, constraint: Constraint) -> Self {
-        self.constraints.push(constraint);
-        self
+
+    /// A field that was previously optional-or-defaulted and is now required
+    /// with no default can no longer resolve old data that omitted it, so
+    /// backward compatibility breaks. Forward still holds: a reader still on
+    /// the (more permissive) writer schema never demanded the field, so it
+    /// has no trouble with data this schema produces, which always includes
+    /// it. The reverse direction (required -> optional/defaulted) never
+    /// loses information a reader needs in either direction, so it isn't
+    /// reported at all.
+    fn classify_field_requiredness(
+        path: &str,
+        reader_field: &FieldDefinition,
+        writer_field: &FieldDefinition,
+        changes: &mut Vec<CompatibilityChange>,
+    ) {
+        if Self::field_explicitly_optional(writer_field) && !Self::field_explicitly_optional(reader_field) {
+            changes.push(CompatibilityChange {
+                path: path.to_string(),
+                message: format!(
+                    "field '{}' became required with no default value; a writer still on the old schema could omit it, so old data predating this change has nothing for the new reader schema to resolve it against",
+                    path
+                ),
+                kind: CompatibilityKind::ForwardOnly,
+            });
+        }
     }
-    
-    /// Set the default value for this field
-    pub fn with_default(mut self, default: CfgppValue) -> Self {
-        self.default_value = Some(default);
-        self
+
+    /// Classifies a type change between a `Double`/`Integer` pair or a type
+    /// and a `Union` containing it as a *directional* widening, not a flatly
+    /// `Compatible` one: the reader only actually promotes data coming from
+    /// the narrower side. If `self` (reader) is the wider side, a reader on
+    /// the old (writer) schema can still be handed data this schema
+    /// produces only as long as it happens to fall in its narrower range -
+    /// not guaranteed - so it's `BackwardOnly`, not `Compatible`. Mirrored
+    /// when `self` is the narrower side (`ForwardOnly`). `OneOf` has no
+    /// widen/narrow rule of its own - its "exactly one branch" semantics
+    /// don't compose with `Union`'s "any branch" the way a type and a
+    /// `Union` containing it do - so a changed `OneOf` falls through to the
+    /// flat `Breaking` case below like any other unrecognized pair.
+    fn classify_field_type(
+        path: &str,
+        reader_type: &TypeDefinition,
+        writer_type: &TypeDefinition,
+        changes: &mut Vec<CompatibilityChange>,
+    ) {
+        if Self::canonical_type(reader_type) == Self::canonical_type(writer_type) {
+            return;
+        }
+
+        // Recurse into matching container shapes so a widening/narrowing
+        // element type (e.g. `Array(Integer)` -> `Array(Double)`) is judged
+        // by the same rules as an unwrapped one, instead of only ever seeing
+        // the container types differ and falling through to `Breaking`.
+        if let (TypeDefinition::Array(reader_element), TypeDefinition::Array(writer_element)) = (reader_type, writer_type) {
+            return Self::classify_field_type(path, reader_element, writer_element, changes);
+        }
+        if let (TypeDefinition::Optional(reader_inner), TypeDefinition::Optional(writer_inner)) = (reader_type, writer_type) {
+            return Self::classify_field_type(path, reader_inner, writer_inner, changes);
+        }
+
+        // Compare against each union member's canonical form, not the raw
+        // `TypeDefinition` - `Union` holds its members in a `Vec`, so two
+        // unions with the same members in a different order (or the same
+        // member nested differently) would otherwise fail a direct `==`
+        // even though they mean the same thing. `Optional(T)` vs `T` is
+        // compared the same way as a single-member "union with null": wrapping
+        // a type in `Optional` widens it, unwrapping narrows it.
+        let reader_wider = matches!((reader_type, writer_type), (TypeDefinition::Double, TypeDefinition::Integer))
+            || matches!(reader_type, TypeDefinition::Union(members) if members.iter().any(|m| Self::canonical_type(m) == Self::canonical_type(writer_type)))
+            || matches!(reader_type, TypeDefinition::Optional(inner) if Self::canonical_type(inner) == Self::canonical_type(writer_type));
+        let reader_narrower = matches!((reader_type, writer_type), (TypeDefinition::Integer, TypeDefinition::Double))
+            || matches!(writer_type, TypeDefinition::Union(members) if members.iter().any(|m| Self::canonical_type(m) == Self::canonical_type(reader_type)))
+            || matches!(writer_type, TypeDefinition::Optional(inner) if Self::canonical_type(inner) == Self::canonical_type(reader_type));
+
+        if reader_wider {
+            changes.push(CompatibilityChange {
+                path: path.to_string(),
+                message: format!(
+                    "field '{}' type widened from {:?} to {:?}; a reader still on the writer schema may not accept everything this schema can now produce",
+                    path, writer_type, reader_type
+                ),
+                kind: CompatibilityKind::BackwardOnly,
+            });
+        } else if reader_narrower {
+            changes.push(CompatibilityChange {
+                path: path.to_string(),
+                message: format!(
+                    "field '{}' type narrowed from {:?} to {:?}; this schema may not accept everything the writer schema could have produced",
+                    path, writer_type, reader_type
+                ),
+                kind: CompatibilityKind::ForwardOnly,
+            });
+        } else {
+            changes.push(CompatibilityChange {
+                path: path.to_string(),
+                message: format!(
+                    "field '{}' type changed from {:?} to {:?}, which isn't a recognized safe widening or narrowing",
+                    path, writer_type, reader_type
+                ),
+                kind: CompatibilityKind::Breaking,
+            });
+        }
     }
-}
 
-impl std::fmt::Display for ValidationError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Validation error at '{}': {}", self.path, self.message)
+    /// Classifies every range-shaped constraint pair (`MinValue`/`MaxValue`,
+    /// `MinLength`/`MaxLength`) the same way as [`Self::classify_field_type`]:
+    /// a narrower range on `self` (reader) only protects readers of *this*
+    /// schema, so writer-side data satisfying the old, looser range isn't
+    /// guaranteed to satisfy the new one (`ForwardOnly` - a reader on the
+    /// writer schema can still read anything this narrower schema produces,
+    /// since it's a subset of what it already accepted). A wider range is
+    /// the mirror (`BackwardOnly`). `Pattern` isn't a range, so a changed
+    /// pattern is reported as flatly `Breaking` instead - this crate has no
+    /// way to statically check whether one regex's language contains
+    /// another's, so it can't say which direction (if either) stays safe.
+    fn classify_field_constraints(
+        path: &str,
+        reader_field: &FieldDefinition,
+        writer_field: &FieldDefinition,
+        changes: &mut Vec<CompatibilityChange>,
+    ) {
+        Self::classify_lower_bound(
+            path,
+            "minimum value",
+            Self::min_value(reader_field).unwrap_or(f64::NEG_INFINITY),
+            Self::min_value(writer_field).unwrap_or(f64::NEG_INFINITY),
+            changes,
+        );
+        Self::classify_upper_bound(
+            path,
+            "maximum value",
+            Self::max_value(reader_field).unwrap_or(f64::INFINITY),
+            Self::max_value(writer_field).unwrap_or(f64::INFINITY),
+            changes,
+        );
+        Self::classify_lower_bound(
+            path,
+            "minimum length",
+            Self::min_length(reader_field).unwrap_or(0) as f64,
+            Self::min_length(writer_field).unwrap_or(0) as f64,
+            changes,
+        );
+        Self::classify_upper_bound(
+            path,
+            "maximum length",
+            Self::max_length(reader_field).unwrap_or(usize::MAX) as f64,
+            Self::max_length(writer_field).unwrap_or(usize::MAX) as f64,
+            changes,
+        );
+
+        if Self::pattern(reader_field).map(Regex::as_str) != Self::pattern(writer_field).map(Regex::as_str) {
+            changes.push(CompatibilityChange {
+                path: path.to_string(),
+                message: format!(
+                    "field '{}' pattern constraint changed; regex containment can't be checked statically, so this is conservatively treated as unsafe in both directions",
+                    path
+                ),
+                kind: CompatibilityKind::Breaking,
+            });
+        }
+
+        if Self::custom_validator(reader_field) != Self::custom_validator(writer_field) {
+            changes.push(CompatibilityChange {
+                path: path.to_string(),
+                message: format!(
+                    "field '{}' custom validator changed; this crate can't inspect what a named validator actually checks, so this is conservatively treated as unsafe in both directions",
+                    path
+                ),
+                kind: CompatibilityKind::Breaking,
+            });
+        }
+
+        if Self::format_kind(reader_field) != Self::format_kind(writer_field) {
+            changes.push(CompatibilityChange {
+                path: path.to_string(),
+                message: format!(
+                    "field '{}' format constraint changed; this crate can't check whether one format's accepted strings are a subset of another's, so this is conservatively treated as unsafe in both directions",
+                    path
+                ),
+                kind: CompatibilityKind::Breaking,
+            });
+        }
+
+        if Self::named_format(reader_field) != Self::named_format(writer_field) {
+            changes.push(CompatibilityChange {
+                path: path.to_string(),
+                message: format!(
+                    "field '{}' named format constraint changed; this crate can't inspect what a registered format validator actually checks, so this is conservatively treated as unsafe in both directions",
+                    path
+                ),
+                kind: CompatibilityKind::Breaking,
+            });
+        }
     }
-}
 
-impl std::error::Error for ValidationError {}
+    fn format_kind(field: &FieldDefinition) -> Option<FormatKind> {
+        field.constraints.iter().find_map(|c| match c {
+            Constraint::Format(kind) => Some(*kind),
+            _ => None,
+        })
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn custom_validator(field: &FieldDefinition) -> Option<&str> {
+        field.constraints.iter().find_map(|c| match c {
+            Constraint::Custom(name) => Some(name.as_str()),
+            _ => None,
+        })
+    }
 
-    #[test]
-    fn test_basic_schema_validation() {
-        let mut schema = Schema::new();
-        
-        // Add enum definition
-        schema.add_enum("Status".to_string(), vec!["active".to_string(), "inactive".to_string()]);
-        
-        // Add object schema
-        let mut fields = HashMap::new();
-        fields.insert("name".to_string(), FieldDefinition::new(TypeDefinition::String, true));
-        fields.insert("status".to_string(), FieldDefinition::new(TypeDefinition::Enum("Status".to_string()), true));
-        schema.add_object_schema("User".to_string(), fields);
-        
-        // Create test value
-        let mut user_obj = HashMap::new();
-        user_obj.insert("name".to_string(), CfgppValue::string("John"));
-        user_obj.insert("status".to_string(), CfgppValue::enum_value("active"));
-        let user_value = CfgppValue::object_with_values(user_obj);
-        
-        // Validate - this should pass
-        schema.set_root_schema(TypeDefinition::Object("User".to_string()));
-        let result = schema.validate(&user_value);
-        assert!(result.is_ok());
+    fn named_format(field: &FieldDefinition) -> Option<&str> {
+        field.constraints.iter().find_map(|c| match c {
+            Constraint::NamedFormat(name) => Some(name.as_str()),
+            _ => None,
+        })
     }
 
-    #[test]
-    fn test_schema_parsing() {
-        let schema_text = r#"
-        enum Status {
-            active, inactive, pending
+    /// A bound where raising it (reader > writer) makes the range
+    /// narrower, e.g. `MinValue`/`MinLength`.
+    fn classify_lower_bound(path: &str, label: &str, reader: f64, writer: f64, changes: &mut Vec<CompatibilityChange>) {
+        if reader > writer {
+            changes.push(CompatibilityChange {
+                path: path.to_string(),
+                message: format!(
+                    "field '{}' {} raised from {} to {}; a reader on the writer schema still accepts anything this schema can produce, but writer-side data below the new bound fails against this schema",
+                    path, label, writer, reader
+                ),
+                kind: CompatibilityKind::ForwardOnly,
+            });
+        } else if reader < writer {
+            changes.push(CompatibilityChange {
+                path: path.to_string(),
+                message: format!(
+                    "field '{}' {} lowered from {} to {}; this schema accepts writer-side data fine, but a reader still on the writer schema may reject the wider range this schema can now produce",
+                    path, label, writer, reader
+                ),
+                kind: CompatibilityKind::BackwardOnly,
+            });
         }
-        
-        User {
-            name: string;
-            age: integer;
-            status: Status;
+    }
+
+    /// A bound where lowering it (reader < writer) makes the range
+    /// narrower, e.g. `MaxValue`/`MaxLength`.
+    fn classify_upper_bound(path: &str, label: &str, reader: f64, writer: f64, changes: &mut Vec<CompatibilityChange>) {
+        if reader < writer {
+            changes.push(CompatibilityChange {
+                path: path.to_string(),
+                message: format!(
+                    "field '{}' {} lowered from {} to {}; a reader on the writer schema still accepts anything this schema can produce, but writer-side data above the new bound fails against this schema",
+                    path, label, writer, reader
+                ),
+                kind: CompatibilityKind::ForwardOnly,
+            });
+        } else if reader > writer {
+            changes.push(CompatibilityChange {
+                path: path.to_string(),
+                message: format!(
+                    "field '{}' {} raised from {} to {}; this schema accepts writer-side data fine, but a reader still on the writer schema may reject the wider range this schema can now produce",
+                    path, label, writer, reader
+                ),
+                kind: CompatibilityKind::BackwardOnly,
+            });
         }
-        "#;
-        
-        let schema = Schema::parse(schema_text).unwrap();
-        
-        assert!(schema.enum_defs.contains_key("Status"));
-        assert!(schema.object_schemas.contains_key("User"));
-        
-        let status_values = &schema.enum_defs["Status"];
-        assert_eq!(status_values.len(), 3);
-        assert!(status_values.contains(&"active".to_string()));
     }
 
-    #[test]
-    fn test_validation_errors() {
-        let mut schema = Schema::new();
-        
-        let mut fields = HashMap::new();
-        fields.insert("required_field".to_string(), FieldDefinition::new(TypeDefinition::String, true));
-        schema.add_object_schema("Test".to_string(), fields);
-        schema.set_root_schema(TypeDefinition::Object("Test".to_string()));
-        
+    fn min_value(field: &FieldDefinition) -> Option<f64> {
+        field.constraints.iter().find_map(|c| match c {
+            Constraint::MinValue(v) => Some(*v),
+            _ => None,
+        })
+    }
+
+    fn max_value(field: &FieldDefinition) -> Option<f64> {
+        field.constraints.iter().find_map(|c| match c {
+            Constraint::MaxValue(v) => Some(*v),
+            _ => None,
+        })
+    }
+
+    fn min_length(field: &FieldDefinition) -> Option<usize> {
+        field.constraints.iter().find_map(|c| match c {
+            Constraint::MinLength(v) => Some(*v),
+            _ => None,
+        })
+    }
+
+    fn max_length(field: &FieldDefinition) -> Option<usize> {
+        field.constraints.iter().find_map(|c| match c {
+            Constraint::MaxLength(v) => Some(*v),
+            _ => None,
+        })
+    }
+
+    fn pattern(field: &FieldDefinition) -> Option<&Regex> {
+        field.constraints.iter().find_map(|c| match c {
+            Constraint::Pattern(re) => Some(re),
+            _ => None,
+        })
+    }
+
+    fn check_enum_compatibility(&self, writer: &Schema, changes: &mut Vec<CompatibilityChange>) {
+        for (enum_name, reader_values) in &self.enum_defs {
+            let Some(writer_values) = writer.enum_defs.get(enum_name) else {
+                changes.push(CompatibilityChange {
+                    path: format!("enum {}", enum_name),
+                    message: format!(
+                        "enum '{}' is new; any field that referenced it by this name under the writer schema would not have resolved",
+                        enum_name
+                    ),
+                    kind: CompatibilityKind::Compatible,
+                });
+                continue;
+            };
+
+            let removed: Vec<&str> = writer_values
+                .iter()
+                .filter(|v| !reader_values.contains(v))
+                .map(String::as_str)
+                .collect();
+            if !removed.is_empty() {
+                changes.push(CompatibilityChange {
+                    path: format!("enum {}", enum_name),
+                    message: format!(
+                        "enum '{}' no longer includes value(s) {}; a reader on this schema can't resolve old data using one of them, but a reader still on the writer schema understands everything this schema can now produce",
+                        enum_name,
+                        removed.join(", ")
+                    ),
+                    kind: CompatibilityKind::ForwardOnly,
+                });
+            }
+
+            let added: Vec<&str> = reader_values
+                .iter()
+                .filter(|v| !writer_values.contains(v))
+                .map(String::as_str)
+                .collect();
+            if !added.is_empty() {
+                changes.push(CompatibilityChange {
+                    path: format!("enum {}", enum_name),
+                    message: format!(
+                        "enum '{}' gained value(s) {}; a writer still on the old schema can't produce them, but existing data still reads fine",
+                        enum_name,
+                        added.join(", ")
+                    ),
+                    kind: CompatibilityKind::BackwardOnly,
+                });
+            }
+        }
+
+        for enum_name in writer.enum_defs.keys() {
+            if !self.enum_defs.contains_key(enum_name) {
+                changes.push(CompatibilityChange {
+                    path: format!("enum {}", enum_name),
+                    message: format!(
+                        "enum '{}' was removed; any field still referencing it by this name under the writer schema can no longer resolve",
+                        enum_name
+                    ),
+                    kind: CompatibilityKind::Breaking,
+                });
+            }
+        }
+    }
+
+    fn check_enum_def(name: &str, values: &[String], errors: &mut Vec<ValidationDiagnostic>) {
+        if values.is_empty() {
+            errors.push(ValidationDiagnostic {
+                path: JsonPath::opaque(format!("enum {}", name)),
+                code: ErrorCode::ConstraintViolation,
+                message: format!("Enum '{}' has no values", name),
+                expected_type: None,
+                actual_type: None,
+                span: None,
+                severity: Severity::Error,
+            });
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for value in values {
+            if !seen.insert(value) {
+                errors.push(ValidationDiagnostic {
+                    path: JsonPath::opaque(format!("enum {}", name)),
+                    code: ErrorCode::ConstraintViolation,
+                    message: format!("Enum '{}' has duplicate value '{}'", name, value),
+                    expected_type: None,
+                    actual_type: None,
+                    span: None,
+                    severity: Severity::Error,
+                });
+            }
+        }
+    }
+
+    fn check_type_def(&self, type_def: &TypeDefinition, path: &str, errors: &mut Vec<ValidationDiagnostic>) {
+        match type_def {
+            TypeDefinition::Object(name) => {
+                if !self.object_schemas.contains_key(name) {
+                    errors.push(ValidationDiagnostic {
+                        path: JsonPath::opaque(path),
+                        code: ErrorCode::InvalidReference,
+                        message: format!("References undefined object schema '{}'", name),
+                        expected_type: Some(format!("object({})", name)),
+                        actual_type: None,
+                        span: None,
+                        severity: Severity::Error,
+                    });
+                }
+            }
+            TypeDefinition::Enum(name) => {
+                if !self.enum_defs.contains_key(name) {
+                    errors.push(ValidationDiagnostic {
+                        path: JsonPath::opaque(path),
+                        code: ErrorCode::InvalidReference,
+                        message: format!("References undefined enum '{}'", name),
+                        expected_type: Some(format!("enum({})", name)),
+                        actual_type: None,
+                        span: None,
+                        severity: Severity::Error,
+                    });
+                }
+            }
+            TypeDefinition::Tuple(name) => {
+                if !self.tuple_schemas.contains_key(name) {
+                    errors.push(ValidationDiagnostic {
+                        path: JsonPath::opaque(path),
+                        code: ErrorCode::InvalidReference,
+                        message: format!("References undefined tuple schema '{}'", name),
+                        expected_type: Some(format!("tuple({})", name)),
+                        actual_type: None,
+                        span: None,
+                        severity: Severity::Error,
+                    });
+                }
+            }
+            TypeDefinition::Array(inner) | TypeDefinition::Optional(inner) => {
+                self.check_type_def(inner, path, errors);
+            }
+            TypeDefinition::Union(types) | TypeDefinition::OneOf(types) => {
+                for union_type in types {
+                    self.check_type_def(union_type, path, errors);
+                }
+            }
+            TypeDefinition::Null
+            | TypeDefinition::Boolean
+            | TypeDefinition::Integer
+            | TypeDefinition::Double
+            | TypeDefinition::String => {}
+        }
+    }
+
+    /// Flags constraints that can never do anything at validation time:
+    /// either because [`Schema::validate_constraint`] only applies them to
+    /// certain `CfgppValue` variants (e.g. `MinLength` only inspects
+    /// `as_string()`), or because a `Custom` constraint names a validator
+    /// that was never registered via [`Schema::register_validator`], which
+    /// would otherwise only surface as a validation error the first time
+    /// `validate()` runs against data.
+    fn check_constraints(&self, field_def: &FieldDefinition, path: &str, errors: &mut Vec<ValidationDiagnostic>) {
+        let base_type = Self::unwrap_optional(&field_def.field_type);
+
+        for constraint in &field_def.constraints {
+            let compatible = match constraint {
+                Constraint::MinLength(_) | Constraint::MaxLength(_) | Constraint::Pattern(_) | Constraint::Format(_) => {
+                    matches!(base_type, TypeDefinition::String)
+                }
+                Constraint::MinValue(_) | Constraint::MaxValue(_) => {
+                    matches!(base_type, TypeDefinition::Integer | TypeDefinition::Double)
+                }
+                Constraint::NamedFormat(name) => {
+                    if self.format_validators.get(name).is_none() {
+                        errors.push(ValidationDiagnostic {
+                            path: JsonPath::opaque(path),
+                            code: ErrorCode::InvalidReference,
+                            message: format!("No format validator registered for '{}'", name),
+                            expected_type: None,
+                            actual_type: None,
+                            span: None,
+                            severity: Severity::Error,
+                        });
+                    }
+                    matches!(base_type, TypeDefinition::String)
+                }
+                Constraint::Custom(name) => {
+                    if self.validators.get(name).is_none() {
+                        errors.push(ValidationDiagnostic {
+                            path: JsonPath::opaque(path),
+                            code: ErrorCode::InvalidReference,
+                            message: format!("No custom validator registered for '{}'", name),
+                            expected_type: None,
+                            actual_type: None,
+                            span: None,
+                            severity: Severity::Error,
+                        });
+                    }
+                    true
+                }
+            };
+
+            if !compatible {
+                errors.push(ValidationDiagnostic {
+                    path: JsonPath::opaque(path),
+                    code: ErrorCode::ConstraintViolation,
+                    message: format!(
+                        "Constraint {:?} can never apply to field type {:?}",
+                        constraint, field_def.field_type
+                    ),
+                    expected_type: None,
+                    actual_type: Some(format!("{:?}", field_def.field_type)),
+                    span: None,
+                    severity: Severity::Error,
+                });
+            }
+        }
+    }
+
+    fn unwrap_optional(type_def: &TypeDefinition) -> &TypeDefinition {
+        match type_def {
+            TypeDefinition::Optional(inner) => Self::unwrap_optional(inner),
+            other => other,
+        }
+    }
+
+    /// Build a graph of object schema -> the object types reachable through
+    /// only its required, non-`Optional` fields, and flag any cycle: such a
+    /// schema requires an infinitely nested value to satisfy one of its own
+    /// required fields, so no finite piece of data could ever validate.
+    fn check_required_cycles(&self, errors: &mut Vec<ValidationDiagnostic>) {
+        let mut graph: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (object_name, fields) in &self.object_schemas {
+            let mut required_refs = Vec::new();
+            for field_def in fields.values() {
+                if field_def.required {
+                    Self::collect_required_object_refs(&field_def.field_type, &mut required_refs);
+                }
+            }
+            graph.insert(object_name.as_str(), required_refs);
+        }
+
+        let mut state: HashMap<&str, CycleState> = HashMap::new();
+        let mut reported = std::collections::HashSet::new();
+
+        let mut starts: Vec<&str> = graph.keys().copied().collect();
+        starts.sort_unstable();
+        for start in starts {
+            if state.get(start) != Some(&CycleState::Done) {
+                let mut stack = Vec::new();
+                Self::dfs_required_cycle(start, &graph, &mut state, &mut stack, &mut reported, errors);
+            }
+        }
+    }
+
+    /// Gathers the object-schema names that a required field forces a
+    /// validator to instantiate, recursing into arrays/unions that can't
+    /// avoid doing so (a union only forces it if *every* arm is an object
+    /// reference; an array doesn't, since it can always be empty).
+    fn collect_required_object_refs<'a>(type_def: &'a TypeDefinition, out: &mut Vec<&'a str>) {
+        match type_def {
+            TypeDefinition::Object(name) => out.push(name.as_str()),
+            TypeDefinition::Union(types) | TypeDefinition::OneOf(types)
+                if types.iter().all(|t| matches!(t, TypeDefinition::Object(_))) =>
+            {
+                for union_type in types {
+                    Self::collect_required_object_refs(union_type, out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn dfs_required_cycle<'a>(
+        node: &'a str,
+        graph: &HashMap<&'a str, Vec<&'a str>>,
+        state: &mut HashMap<&'a str, CycleState>,
+        stack: &mut Vec<&'a str>,
+        reported: &mut std::collections::HashSet<Vec<&'a str>>,
+        errors: &mut Vec<ValidationDiagnostic>,
+    ) {
+        state.insert(node, CycleState::Visiting);
+        stack.push(node);
+
+        if let Some(neighbors) = graph.get(node) {
+            for &next in neighbors {
+                match state.get(next) {
+                    Some(CycleState::Visiting) => {
+                        let cycle_start = stack.iter().position(|&n| n == next).unwrap_or(0);
+                        let mut cycle: Vec<&str> = stack[cycle_start..].to_vec();
+                        cycle.push(next);
+                        if reported.insert(cycle.clone()) {
+                            errors.push(ValidationDiagnostic {
+                                path: JsonPath::opaque(cycle.join(" -> ")),
+                                code: ErrorCode::InvalidReference,
+                                message: format!(
+                                    "Object schemas form a required-only reference cycle ({}) that no finite value can satisfy",
+                                    cycle.join(" -> ")
+                                ),
+                                expected_type: None,
+                                actual_type: None,
+                                span: None,
+                                severity: Severity::Error,
+                            });
+                        }
+                    }
+                    Some(CycleState::Done) => {}
+                    None => Self::dfs_required_cycle(next, graph, state, stack, reported, errors),
+                }
+            }
+        }
+
+        stack.pop();
+        state.insert(node, CycleState::Done);
+    }
+
+    fn parse_type_definition(type_str: &str) -> CfgppResult<TypeDefinition> {
+        let trimmed = type_str.trim();
+        
+        match trimmed {
+            "null" => Ok(TypeDefinition::Null),
+            "boolean" => Ok(TypeDefinition::Boolean),
+            "integer" => Ok(TypeDefinition::Integer),
+            "double" => Ok(TypeDefinition::Double),
+            "string" => Ok(TypeDefinition::String),
+            _ => {
+                // Handle array types like "array<string>"
+                if trimmed.starts_with("array<") && trimmed.ends_with('>') {
+                    let inner_type = &trimmed[6..trimmed.len() - 1];
+                    let element_type = Self::parse_type_definition(inner_type)?;
+                    return Ok(TypeDefinition::Array(Box::new(element_type)));
+                }
+                
+                // Handle optional types like "optional<string>"
+                if trimmed.starts_with("optional<") && trimmed.ends_with('>') {
+                    let inner_type = &trimmed[9..trimmed.len() - 1];
+                    let element_type = Self::parse_type_definition(inner_type)?;
+                    return Ok(TypeDefinition::Optional(Box::new(element_type)));
+                }
+                
+                // Assume it's a custom type (object or enum)
+                Ok(TypeDefinition::Object(trimmed.to_string()))
+            }
+        }
+    }
+
+    /// Export this schema as a Draft 2020-12 JSON Schema document. Object and
+    /// enum schemas are emitted under `$defs` and referenced with `$ref`;
+    /// tuple schemas are emitted the same way as a `prefixItems` array, with
+    /// `minItems` marking how many leading slots are required; `Union`
+    /// becomes `anyOf`; `OneOf` becomes `oneOf`; `Optional(T)` becomes
+    /// `anyOf: [T, {type: null}]`. `Constraint::Format` maps directly onto the
+    /// standard `format` keyword; `Constraint::NamedFormat` reuses the same
+    /// `format` keyword, since from JSON Schema's perspective both are just a
+    /// format name - only `Schema::from_json_schema`'s lookup into
+    /// `FormatKind` vs. the format-validator registry differs. `Constraint::Custom`
+    /// has no JSON Schema keyword equivalent, so it's preserved as the vendor
+    /// extension `x-cfgpp-custom-validator` to survive a round trip through
+    /// [`Schema::from_json_schema`]. A `default_value`
+    /// round-trips as the `default` keyword for scalar values; container and
+    /// vendor variants (`Array`, `Object`, `Raw`, `BigNumber`, `SizedInteger`,
+    /// `UInteger`)
+    /// have no unambiguous JSON equivalent and are simply omitted.
+    #[cfg(feature = "schema-validation")]
+    pub fn to_json_schema(&self) -> serde_json::Value {
+        let mut defs = serde_json::Map::new();
+
+        for (name, values) in &self.enum_defs {
+            defs.insert(
+                name.clone(),
+                serde_json::json!({
+                    "type": "string",
+                    "enum": values,
+                }),
+            );
+        }
+
+        for (name, fields) in &self.object_schemas {
+            let mut properties = serde_json::Map::new();
+            let mut required: Vec<&str> = Vec::new();
+            for (field_name, field_def) in fields {
+                properties.insert(field_name.clone(), Self::field_def_to_json_schema(field_def));
+                if field_def.required {
+                    required.push(field_name);
+                }
+            }
+            required.sort_unstable();
+
+            defs.insert(
+                name.clone(),
+                serde_json::json!({
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                }),
+            );
+        }
+
+        for (name, slots) in &self.tuple_schemas {
+            let prefix_items: Vec<serde_json::Value> = slots.iter().map(Self::field_def_to_json_schema).collect();
+            let min_items = slots.iter().rposition(|slot| slot.required).map(|i| i + 1).unwrap_or(0);
+
+            defs.insert(
+                name.clone(),
+                serde_json::json!({
+                    "type": "array",
+                    "prefixItems": prefix_items,
+                    "minItems": min_items,
+                    "maxItems": slots.len(),
+                }),
+            );
+        }
+
+        let mut document = match &self.root_schema {
+            Some(type_def) => Self::type_def_to_json_schema(type_def),
+            None => serde_json::json!({}),
+        };
+
+        if let serde_json::Value::Object(ref mut map) = document {
+            map.insert(
+                "$schema".to_string(),
+                serde_json::Value::String("https://json-schema.org/draft/2020-12/schema".to_string()),
+            );
+            if !defs.is_empty() {
+                map.insert("$defs".to_string(), serde_json::Value::Object(defs));
+            }
+        }
+
+        document
+    }
+
+    /// Import a schema from a Draft 2020-12 JSON Schema document, reversing
+    /// [`Schema::to_json_schema`]'s mapping: `$defs` entries with an `enum`
+    /// array become `enum_defs`, `$defs` entries with `properties` become
+    /// `object_schemas`, `$defs` entries with `prefixItems` become
+    /// `tuple_schemas`, and the document's own `type`/`$ref`/`anyOf`/`oneOf`
+    /// become the root schema.
+    #[cfg(feature = "schema-validation")]
+    pub fn from_json_schema(document: &serde_json::Value) -> CfgppResult<Self> {
+        let mut schema = Self::new();
+        let defs = document.get("$defs").and_then(|d| d.as_object());
+
+        if let Some(defs) = defs {
+            for (name, def) in defs {
+                if let Some(values) = def.get("enum").and_then(|v| v.as_array()) {
+                    let values = values
+                        .iter()
+                        .map(|v| {
+                            v.as_str().map(str::to_string).ok_or_else(|| {
+                                CfgppError::parse_error(format!(
+                                    "$defs.{} has a non-string enum value",
+                                    name
+                                ))
+                            })
+                        })
+                        .collect::<CfgppResult<Vec<_>>>()?;
+                    schema.add_enum(name.clone(), values);
+                }
+            }
+
+            for (name, def) in defs {
+                if def.get("properties").is_some() {
+                    let fields = Self::json_schema_to_fields(def, name, Some(defs))?;
+                    schema.add_object_schema(name.clone(), fields);
+                } else if def.get("prefixItems").is_some() {
+                    let slots = Self::json_schema_to_tuple_slots(def, name, Some(defs))?;
+                    schema.add_tuple_schema(name.clone(), slots);
+                }
+            }
+        }
+
+        let is_root_schema = document
+            .as_object()
+            .map(|map| {
+                map.contains_key("type")
+                    || map.contains_key("$ref")
+                    || map.contains_key("anyOf")
+                    || map.contains_key("oneOf")
+            })
+            .unwrap_or(false);
+        if is_root_schema {
+            schema.root_schema = Some(Self::json_schema_to_type_def(document, defs)?);
+        }
+
+        Ok(schema)
+    }
+
+    #[cfg(feature = "schema-validation")]
+    fn type_def_to_json_schema(type_def: &TypeDefinition) -> serde_json::Value {
+        match type_def {
+            TypeDefinition::Null => serde_json::json!({"type": "null"}),
+            TypeDefinition::Boolean => serde_json::json!({"type": "boolean"}),
+            TypeDefinition::Integer => serde_json::json!({"type": "integer"}),
+            TypeDefinition::Double => serde_json::json!({"type": "number"}),
+            TypeDefinition::String => serde_json::json!({"type": "string"}),
+            TypeDefinition::Array(element_type) => serde_json::json!({
+                "type": "array",
+                "items": Self::type_def_to_json_schema(element_type),
+            }),
+            TypeDefinition::Object(name) => serde_json::json!({"$ref": format!("#/$defs/{}", name)}),
+            TypeDefinition::Tuple(name) => serde_json::json!({"$ref": format!("#/$defs/{}", name)}),
+            TypeDefinition::Enum(name) => serde_json::json!({"$ref": format!("#/$defs/{}", name)}),
+            TypeDefinition::Union(types) => serde_json::json!({
+                "anyOf": types.iter().map(Self::type_def_to_json_schema).collect::<Vec<_>>(),
+            }),
+            TypeDefinition::OneOf(types) => serde_json::json!({
+                "oneOf": types.iter().map(Self::type_def_to_json_schema).collect::<Vec<_>>(),
+            }),
+            TypeDefinition::Optional(inner_type) => serde_json::json!({
+                "anyOf": [Self::type_def_to_json_schema(inner_type), serde_json::json!({"type": "null"})],
+            }),
+        }
+    }
+
+    #[cfg(feature = "schema-validation")]
+    fn field_def_to_json_schema(field_def: &FieldDefinition) -> serde_json::Value {
+        let mut property = Self::type_def_to_json_schema(&field_def.field_type);
+
+        if let serde_json::Value::Object(ref mut map) = property {
+            for constraint in &field_def.constraints {
+                match constraint {
+                    Constraint::MinLength(n) => {
+                        map.insert("minLength".to_string(), serde_json::json!(n));
+                    }
+                    Constraint::MaxLength(n) => {
+                        map.insert("maxLength".to_string(), serde_json::json!(n));
+                    }
+                    Constraint::MinValue(n) => {
+                        map.insert("minimum".to_string(), serde_json::json!(n));
+                    }
+                    Constraint::MaxValue(n) => {
+                        map.insert("maximum".to_string(), serde_json::json!(n));
+                    }
+                    Constraint::Pattern(regex) => {
+                        map.insert("pattern".to_string(), serde_json::json!(regex.as_str()));
+                    }
+                    Constraint::Format(kind) => {
+                        map.insert("format".to_string(), serde_json::json!(kind.as_str()));
+                    }
+                    Constraint::NamedFormat(name) => {
+                        map.insert("format".to_string(), serde_json::json!(name));
+                    }
+                    Constraint::Custom(name) => {
+                        map.insert("x-cfgpp-custom-validator".to_string(), serde_json::json!(name));
+                    }
+                }
+            }
+
+            if let Some(default_value) = &field_def.default_value {
+                if let Some(json_default) = Self::scalar_to_json_schema(default_value) {
+                    map.insert("default".to_string(), json_default);
+                }
+            }
+
+            if !field_def.aliases.is_empty() {
+                map.insert("x-cfgpp-aliases".to_string(), serde_json::json!(field_def.aliases));
+            }
+        }
+
+        property
+    }
+
+    /// Convert a scalar [`CfgppValue`] to its JSON Schema `default` keyword
+    /// representation. Returns `None` for container/vendor variants
+    /// (`Array`, `Object`, `Raw`, `BigNumber`, `SizedInteger`, `UInteger`) that have no
+    /// lossless, unambiguous JSON equivalent - a default of that shape is
+    /// simply omitted from the exported document rather than approximated.
+    #[cfg(feature = "schema-validation")]
+    fn scalar_to_json_schema(value: &CfgppValue) -> Option<serde_json::Value> {
+        match value {
+            CfgppValue::Null => Some(serde_json::Value::Null),
+            CfgppValue::Boolean(b) => Some(serde_json::json!(b)),
+            CfgppValue::Integer(i) => Some(serde_json::json!(i)),
+            CfgppValue::Double(d) => Some(serde_json::json!(d)),
+            CfgppValue::String(s) | CfgppValue::Enum(s) => Some(serde_json::json!(s)),
+            _ => None,
+        }
+    }
+
+    /// Reverse of [`Schema::scalar_to_json_schema`].
+    #[cfg(feature = "schema-validation")]
+    fn json_schema_to_scalar(value: &serde_json::Value) -> Option<CfgppValue> {
+        match value {
+            serde_json::Value::Null => Some(CfgppValue::Null),
+            serde_json::Value::Bool(b) => Some(CfgppValue::Boolean(*b)),
+            serde_json::Value::Number(n) => {
+                if let Some(i) = n.as_i64() {
+                    Some(CfgppValue::Integer(i))
+                } else {
+                    n.as_f64().map(CfgppValue::Double)
+                }
+            }
+            serde_json::Value::String(s) => Some(CfgppValue::String(s.clone())),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "schema-validation")]
+    fn json_schema_to_fields(
+        def: &serde_json::Value,
+        schema_name: &str,
+        defs: Option<&serde_json::Map<String, serde_json::Value>>,
+    ) -> CfgppResult<HashMap<String, FieldDefinition>> {
+        let properties = def.get("properties").and_then(|v| v.as_object()).ok_or_else(|| {
+            CfgppError::parse_error(format!("$defs.{} is missing 'properties'", schema_name))
+        })?;
+        let required: std::collections::HashSet<&str> = def
+            .get("required")
+            .and_then(|v| v.as_array())
+            .map(|values| values.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+
+        let mut fields = HashMap::new();
+        for (field_name, field_schema) in properties {
+            let field_type = Self::json_schema_to_type_def(field_schema, defs)?;
+            let mut field_def = FieldDefinition::new(field_type, required.contains(field_name.as_str()));
+            for constraint in Self::json_schema_to_constraints(field_schema)? {
+                field_def = field_def.with_constraint(constraint);
+            }
+            if let Some(default_value) = field_schema.get("default").and_then(Self::json_schema_to_scalar) {
+                field_def = field_def.with_default(default_value);
+            }
+            if let Some(aliases) = field_schema.get("x-cfgpp-aliases").and_then(|v| v.as_array()) {
+                for alias in aliases.iter().filter_map(|v| v.as_str()) {
+                    field_def = field_def.with_alias(alias);
+                }
+            }
+            fields.insert(field_name.clone(), field_def);
+        }
+        Ok(fields)
+    }
+
+    #[cfg(feature = "schema-validation")]
+    fn json_schema_to_tuple_slots(
+        def: &serde_json::Value,
+        schema_name: &str,
+        defs: Option<&serde_json::Map<String, serde_json::Value>>,
+    ) -> CfgppResult<Vec<FieldDefinition>> {
+        let prefix_items = def.get("prefixItems").and_then(|v| v.as_array()).ok_or_else(|| {
+            CfgppError::parse_error(format!("$defs.{} is missing 'prefixItems'", schema_name))
+        })?;
+        let min_items = def.get("minItems").and_then(|v| v.as_u64()).unwrap_or(prefix_items.len() as u64) as usize;
+
+        prefix_items
+            .iter()
+            .enumerate()
+            .map(|(index, slot_schema)| {
+                let slot_type = Self::json_schema_to_type_def(slot_schema, defs)?;
+                let mut slot_def = FieldDefinition::new(slot_type, index < min_items);
+                for constraint in Self::json_schema_to_constraints(slot_schema)? {
+                    slot_def = slot_def.with_constraint(constraint);
+                }
+                Ok(slot_def)
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "schema-validation")]
+    fn json_schema_to_type_def(
+        schema: &serde_json::Value,
+        defs: Option<&serde_json::Map<String, serde_json::Value>>,
+    ) -> CfgppResult<TypeDefinition> {
+        if let Some(reference) = schema.get("$ref").and_then(|v| v.as_str()) {
+            let name = reference.strip_prefix("#/$defs/").ok_or_else(|| {
+                CfgppError::parse_error(format!("Unsupported $ref '{}', expected '#/$defs/<name>'", reference))
+            })?;
+            let referenced_def = defs.and_then(|defs| defs.get(name));
+            let is_enum = referenced_def.map(|def| def.get("enum").is_some()).unwrap_or(false);
+            let is_tuple = referenced_def.map(|def| def.get("prefixItems").is_some()).unwrap_or(false);
+            return Ok(if is_enum {
+                TypeDefinition::Enum(name.to_string())
+            } else if is_tuple {
+                TypeDefinition::Tuple(name.to_string())
+            } else {
+                TypeDefinition::Object(name.to_string())
+            });
+        }
+
+        if let Some(variants) = schema.get("oneOf").and_then(|v| v.as_array()) {
+            let types = variants
+                .iter()
+                .map(|variant| Self::json_schema_to_type_def(variant, defs))
+                .collect::<CfgppResult<Vec<_>>>()?;
+            return Ok(TypeDefinition::OneOf(types));
+        }
+
+        if let Some(variants) = schema.get("anyOf").and_then(|v| v.as_array()) {
+            if let [first, second] = variants.as_slice() {
+                if second.get("type").and_then(|v| v.as_str()) == Some("null") {
+                    return Ok(TypeDefinition::Optional(Box::new(Self::json_schema_to_type_def(first, defs)?)));
+                }
+                if first.get("type").and_then(|v| v.as_str()) == Some("null") {
+                    return Ok(TypeDefinition::Optional(Box::new(Self::json_schema_to_type_def(second, defs)?)));
+                }
+            }
+            let types = variants
+                .iter()
+                .map(|variant| Self::json_schema_to_type_def(variant, defs))
+                .collect::<CfgppResult<Vec<_>>>()?;
+            return Ok(TypeDefinition::Union(types));
+        }
+
+        match schema.get("type").and_then(|v| v.as_str()) {
+            Some("null") => Ok(TypeDefinition::Null),
+            Some("boolean") => Ok(TypeDefinition::Boolean),
+            Some("integer") => Ok(TypeDefinition::Integer),
+            Some("number") => Ok(TypeDefinition::Double),
+            Some("string") => Ok(TypeDefinition::String),
+            Some("array") => {
+                let items = schema
+                    .get("items")
+                    .ok_or_else(|| CfgppError::parse_error("Array schema is missing 'items'"))?;
+                Ok(TypeDefinition::Array(Box::new(Self::json_schema_to_type_def(items, defs)?)))
+            }
+            other => Err(CfgppError::parse_error(format!(
+                "Unsupported JSON Schema node: {:?}",
+                other
+            ))),
+        }
+    }
+
+    #[cfg(feature = "schema-validation")]
+    fn json_schema_to_constraints(schema: &serde_json::Value) -> CfgppResult<Vec<Constraint>> {
+        let mut constraints = Vec::new();
+
+        if let Some(n) = schema.get("minLength").and_then(|v| v.as_u64()) {
+            constraints.push(Constraint::MinLength(n as usize));
+        }
+        if let Some(n) = schema.get("maxLength").and_then(|v| v.as_u64()) {
+            constraints.push(Constraint::MaxLength(n as usize));
+        }
+        if let Some(n) = schema.get("minimum").and_then(|v| v.as_f64()) {
+            constraints.push(Constraint::MinValue(n));
+        }
+        if let Some(n) = schema.get("maximum").and_then(|v| v.as_f64()) {
+            constraints.push(Constraint::MaxValue(n));
+        }
+        if let Some(pattern) = schema.get("pattern").and_then(|v| v.as_str()) {
+            let regex = Regex::new(pattern)
+                .map_err(|e| CfgppError::parse_error(format!("Invalid pattern '{}': {}", pattern, e)))?;
+            constraints.push(Constraint::Pattern(regex));
+        }
+        if let Some(format_str) = schema.get("format").and_then(|v| v.as_str()) {
+            match FormatKind::from_str(format_str) {
+                Some(kind) => constraints.push(Constraint::Format(kind)),
+                None => constraints.push(Constraint::NamedFormat(format_str.to_string())),
+            }
+        }
+        if let Some(name) = schema.get("x-cfgpp-custom-validator").and_then(|v| v.as_str()) {
+            constraints.push(Constraint::Custom(name.to_string()));
+        }
+
+        Ok(constraints)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CycleState {
+    Visiting,
+    Done,
+}
+
+impl Default for Schema {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FieldDefinition {
+    /// Create a new field definition
+    pub fn new(field_type: TypeDefinition, required: bool) -> Self {
+        Self {
+            field_type,
+            required,
+            default_value: None,
+            constraints: Vec::new(),
+            aliases: Vec::new(),
+            guard: None,
+        }
+    }
+
+    /// Add a constraint to this field
+    pub fn with_constraint(mut self, constraint: Constraint) -> Self {
+        self.constraints.push(constraint);
+        self
+    }
+
+    /// Set the default value for this field
+    pub fn with_default(mut self, default: CfgppValue) -> Self {
+        self.default_value = Some(default);
+        self
+    }
+
+    /// Record an earlier name this field was known by, for
+    /// [`Schema::check_compatibility`] to match against.
+    pub fn with_alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// Gate this field's presence/requiredness on `condition`: a required
+    /// field whose guard is false is no longer reported missing when absent,
+    /// and is instead flagged as an error if present anyway.
+    pub fn when(mut self, condition: FieldCondition) -> Self {
+        self.guard = Some(condition);
+        self
+    }
+}
+
+impl std::fmt::Display for ValidationDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Validation error at '{}': {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for ValidationDiagnostic {}
+
+impl ValidationDiagnostic {
+    /// Render this error as a caret-underlined diagnostic against the
+    /// original `source` text it was found in, similar to a compiler's
+    /// `error: <message>` snippet. Falls back to `<severity>: ` followed by
+    /// the `Display` form when `self.span` is `None`, since there's
+    /// no byte range to locate a line in `source`.
+    pub fn render(&self, source: &str) -> String {
+        let Some((start, end)) = self.span else {
+            return format!("{}: {}", self.severity, self);
+        };
+
+        let Some((line_number, byte_column, line_text)) = locate_span(source, start) else {
+            return format!("{}: {}", self.severity, self);
+        };
+
+        // `byte_column`/the span's byte length count UTF-8 bytes, not
+        // printed columns - convert to character counts so the caret line
+        // still lines up under the offending text when anything earlier on
+        // the line is multi-byte.
+        let display_column = char_column(line_text, byte_column);
+        let span_byte_end = (byte_column + end.saturating_sub(start).max(1)).min(line_text.len());
+        let underline_len = char_column(line_text, span_byte_end)
+            .saturating_sub(display_column)
+            .max(1);
+
+        let gutter = format!("{}", line_number);
+        let padding = " ".repeat(gutter.len());
+
+        format!(
+            "{severity}: {message}\n{padding} --> {path} (line {line}, column {column})\n{padding} |\n{line:>width$} | {line_text}\n{padding} | {caret_padding}{carets}",
+            severity = self.severity,
+            message = self.message,
+            path = self.path,
+            padding = padding,
+            line = line_number,
+            column = display_column + 1,
+            width = gutter.len(),
+            line_text = line_text,
+            caret_padding = " ".repeat(display_column),
+            carets = "^".repeat(underline_len),
+        )
+    }
+}
+
+/// Number of `char`s in `line` that start strictly before byte offset
+/// `byte_offset`, used to turn a byte column into a printed column. Safe
+/// even when `byte_offset` isn't itself a char boundary.
+fn char_column(line: &str, byte_offset: usize) -> usize {
+    line.char_indices().take_while(|(i, _)| *i < byte_offset).count()
+}
+
+/// Find the 1-based line number, 0-based column, and full text of the line
+/// containing byte offset `position` in `source`.
+fn locate_span(source: &str, position: usize) -> Option<(usize, usize, &str)> {
+    if position > source.len() || !source.is_char_boundary(position) {
+        return None;
+    }
+
+    let mut line_start = 0;
+    for (line_number, line_text) in source.split('\n').enumerate() {
+        let line_end = line_start + line_text.len();
+        if position <= line_end {
+            let column = position - line_start;
+            return Some((line_number + 1, column, line_text));
+        }
+        line_start = line_end + 1; // account for the '\n' the split consumed
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_basic_schema_validation() {
+        let mut schema = Schema::new();
+        
+        // Add enum definition
+        schema.add_enum("Status".to_string(), vec!["active".to_string(), "inactive".to_string()]);
+        
+        // Add object schema
+        let mut fields = HashMap::new();
+        fields.insert("name".to_string(), FieldDefinition::new(TypeDefinition::String, true));
+        fields.insert("status".to_string(), FieldDefinition::new(TypeDefinition::Enum("Status".to_string()), true));
+        schema.add_object_schema("User".to_string(), fields);
+        
+        // Create test value
+        let mut user_obj = crate::value::CfgppObject::new();
+        user_obj.insert("name".to_string(), CfgppValue::string("John"));
+        user_obj.insert("status".to_string(), CfgppValue::enum_value("active"));
+        let user_value = CfgppValue::object_with_values(user_obj);
+        
+        // Validate - this should pass
+        schema.set_root_schema(TypeDefinition::Object("User".to_string()));
+        let result = schema.validate(&user_value);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_schema_parsing() {
+        let schema_text = r#"
+        enum Status {
+            active, inactive, pending
+        }
+        
+        User {
+            name: string;
+            age: integer;
+            status: Status;
+        }
+        "#;
+        
+        let schema = Schema::parse(schema_text).unwrap();
+        
+        assert!(schema.enum_defs.contains_key("Status"));
+        assert!(schema.object_schemas.contains_key("User"));
+        
+        let status_values = &schema.enum_defs["Status"];
+        assert_eq!(status_values.len(), 3);
+        assert!(status_values.contains(&"active".to_string()));
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let mut schema = Schema::new();
+        
+        let mut fields = HashMap::new();
+        fields.insert("required_field".to_string(), FieldDefinition::new(TypeDefinition::String, true));
+        schema.add_object_schema("Test".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("Test".to_string()));
+        
         // Missing required field
         let empty_obj = CfgppValue::object();
         let result = schema.validate(&empty_obj);
         
         assert!(result.is_err());
         let errors = result.unwrap_err();
-        assert!(!errors.is_empty());
-        assert!(errors[0].message.contains("Required field"));
+        assert!(!errors.is_empty());
+        assert!(errors[0].message.contains("Required field"));
+    }
+
+    #[test]
+    fn test_check_passes_for_consistent_schema() {
+        let mut schema = Schema::new();
+        schema.add_enum("Status".to_string(), vec!["active".to_string(), "inactive".to_string()]);
+
+        let mut fields = HashMap::new();
+        fields.insert("name".to_string(), FieldDefinition::new(TypeDefinition::String, true));
+        fields.insert("status".to_string(), FieldDefinition::new(TypeDefinition::Enum("Status".to_string()), true));
+        schema.add_object_schema("User".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("User".to_string()));
+
+        assert!(schema.check().is_empty());
+    }
+
+    #[test]
+    fn test_check_flags_undefined_object_and_enum_references() {
+        let mut schema = Schema::new();
+
+        let mut fields = HashMap::new();
+        fields.insert("role".to_string(), FieldDefinition::new(TypeDefinition::Enum("Role".to_string()), true));
+        fields.insert("manager".to_string(), FieldDefinition::new(TypeDefinition::Object("Manager".to_string()), true));
+        schema.add_object_schema("User".to_string(), fields);
+
+        let errors = schema.check();
+        assert!(errors.iter().any(|e| e.message.contains("undefined enum 'Role'")));
+        assert!(errors.iter().any(|e| e.message.contains("undefined object schema 'Manager'")));
+    }
+
+    #[test]
+    fn test_check_flags_empty_and_duplicate_enum_values() {
+        let mut schema = Schema::new();
+        schema.add_enum("Empty".to_string(), vec![]);
+        schema.add_enum("Dup".to_string(), vec!["a".to_string(), "a".to_string()]);
+
+        let errors = schema.check();
+        assert!(errors.iter().any(|e| e.message.contains("Enum 'Empty' has no values")));
+        assert!(errors.iter().any(|e| e.message.contains("duplicate value 'a'")));
+    }
+
+    #[test]
+    fn test_check_flags_constraint_on_incompatible_type() {
+        let mut schema = Schema::new();
+
+        let mut fields = HashMap::new();
+        fields.insert(
+            "count".to_string(),
+            FieldDefinition::new(TypeDefinition::Integer, true).with_constraint(Constraint::MinLength(1)),
+        );
+        schema.add_object_schema("Counter".to_string(), fields);
+
+        let errors = schema.check();
+        assert!(errors.iter().any(|e| e.message.contains("can never apply to field type Integer")));
+    }
+
+    #[test]
+    fn test_check_flags_required_only_reference_cycle() {
+        let mut schema = Schema::new();
+
+        let mut a_fields = HashMap::new();
+        a_fields.insert("b".to_string(), FieldDefinition::new(TypeDefinition::Object("B".to_string()), true));
+        schema.add_object_schema("A".to_string(), a_fields);
+
+        let mut b_fields = HashMap::new();
+        b_fields.insert("a".to_string(), FieldDefinition::new(TypeDefinition::Object("A".to_string()), true));
+        schema.add_object_schema("B".to_string(), b_fields);
+
+        let errors = schema.check();
+        assert!(errors.iter().any(|e| e.message.contains("required-only reference cycle")));
+    }
+
+    #[test]
+    fn test_check_allows_optional_self_reference() {
+        let mut schema = Schema::new();
+
+        let mut fields = HashMap::new();
+        fields.insert(
+            "parent".to_string(),
+            FieldDefinition::new(TypeDefinition::Optional(Box::new(TypeDefinition::Object("Node".to_string()))), false),
+        );
+        schema.add_object_schema("Node".to_string(), fields);
+
+        assert!(schema.check().is_empty());
+    }
+
+    #[test]
+    fn test_check_flags_unregistered_custom_validator() {
+        let mut schema = Schema::new();
+
+        let mut fields = HashMap::new();
+        fields.insert(
+            "count".to_string(),
+            FieldDefinition::new(TypeDefinition::Integer, true).with_constraint(Constraint::Custom("missing".to_string())),
+        );
+        schema.add_object_schema("Counter".to_string(), fields);
+
+        let errors = schema.check();
+        assert!(errors.iter().any(|e| e.message.contains("No custom validator registered for 'missing'")));
+    }
+
+    #[test]
+    fn test_check_allows_registered_custom_validator() {
+        let mut schema = Schema::new();
+        schema.register_validator("even", |_value, _ctx| Ok(()));
+
+        let mut fields = HashMap::new();
+        fields.insert(
+            "count".to_string(),
+            FieldDefinition::new(TypeDefinition::Integer, true).with_constraint(Constraint::Custom("even".to_string())),
+        );
+        schema.add_object_schema("Counter".to_string(), fields);
+
+        assert!(schema.check().is_empty());
+    }
+
+    #[test]
+    fn test_custom_validator_passes_valid_value() {
+        let mut schema = Schema::new();
+        schema.register_validator("even", |value, _ctx| match value {
+            CfgppValue::Integer(i) if i % 2 == 0 => Ok(()),
+            CfgppValue::Integer(i) => Err(format!("{} is not even", i)),
+            _ => Err("expected an integer".to_string()),
+        });
+
+        let mut fields = HashMap::new();
+        fields.insert(
+            "count".to_string(),
+            FieldDefinition::new(TypeDefinition::Integer, true).with_constraint(Constraint::Custom("even".to_string())),
+        );
+        schema.add_object_schema("Counter".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("Counter".to_string()));
+
+        let mut obj = crate::value::CfgppObject::new();
+        obj.insert("count".to_string(), CfgppValue::Integer(4));
+        let value = CfgppValue::object_with_values(obj);
+
+        assert!(schema.validate(&value).is_ok());
+    }
+
+    #[test]
+    fn test_custom_validator_reports_failure() {
+        let mut schema = Schema::new();
+        schema.register_validator("even", |value, _ctx| match value {
+            CfgppValue::Integer(i) if i % 2 == 0 => Ok(()),
+            CfgppValue::Integer(i) => Err(format!("{} is not even", i)),
+            _ => Err("expected an integer".to_string()),
+        });
+
+        let mut fields = HashMap::new();
+        fields.insert(
+            "count".to_string(),
+            FieldDefinition::new(TypeDefinition::Integer, true).with_constraint(Constraint::Custom("even".to_string())),
+        );
+        schema.add_object_schema("Counter".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("Counter".to_string()));
+
+        let mut obj = crate::value::CfgppObject::new();
+        obj.insert("count".to_string(), CfgppValue::Integer(3));
+        let value = CfgppValue::object_with_values(obj);
+
+        let errors = schema.validate(&value).unwrap_err();
+        assert!(errors.iter().any(|e| e.message.contains("3 is not even")));
+    }
+
+    #[test]
+    fn test_custom_validator_unregistered_name_is_an_error() {
+        let mut schema = Schema::new();
+
+        let mut fields = HashMap::new();
+        fields.insert(
+            "count".to_string(),
+            FieldDefinition::new(TypeDefinition::Integer, true).with_constraint(Constraint::Custom("missing".to_string())),
+        );
+        schema.add_object_schema("Counter".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("Counter".to_string()));
+
+        let mut obj = crate::value::CfgppObject::new();
+        obj.insert("count".to_string(), CfgppValue::Integer(4));
+        let value = CfgppValue::object_with_values(obj);
+
+        let errors = schema.validate(&value).unwrap_err();
+        assert!(errors.iter().any(|e| e.message.contains("No custom validator registered for 'missing'")));
+    }
+
+    #[test]
+    fn test_custom_validator_can_use_root_for_cross_field_check() {
+        let mut schema = Schema::new();
+        schema.register_validator("after_start", |value, ctx| {
+            let end = value.as_integer().ok_or("end_date must be an integer")?;
+            let start = ctx
+                .root
+                .get("start_date")
+                .and_then(CfgppValue::as_integer)
+                .ok_or("start_date must be an integer")?;
+            if end > start {
+                Ok(())
+            } else {
+                Err(format!("end_date {} must be after start_date {}", end, start))
+            }
+        });
+
+        let mut fields = HashMap::new();
+        fields.insert("start_date".to_string(), FieldDefinition::new(TypeDefinition::Integer, true));
+        fields.insert(
+            "end_date".to_string(),
+            FieldDefinition::new(TypeDefinition::Integer, true).with_constraint(Constraint::Custom("after_start".to_string())),
+        );
+        schema.add_object_schema("Range".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("Range".to_string()));
+
+        let mut obj = crate::value::CfgppObject::new();
+        obj.insert("start_date".to_string(), CfgppValue::Integer(10));
+        obj.insert("end_date".to_string(), CfgppValue::Integer(5));
+        let value = CfgppValue::object_with_values(obj);
+
+        let errors = schema.validate(&value).unwrap_err();
+        assert!(errors.iter().any(|e| e.message.contains("must be after start_date")));
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn test_to_json_schema_maps_object_schema_with_constraints() {
+        let mut schema = Schema::new();
+        let mut fields = HashMap::new();
+        fields.insert(
+            "name".to_string(),
+            FieldDefinition::new(TypeDefinition::String, true).with_constraint(Constraint::MinLength(1)),
+        );
+        fields.insert("nickname".to_string(), FieldDefinition::new(TypeDefinition::Optional(Box::new(TypeDefinition::String)), false));
+        schema.add_object_schema("User".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("User".to_string()));
+
+        let json_schema = schema.to_json_schema();
+
+        assert_eq!(json_schema["$ref"], "#/$defs/User");
+        assert_eq!(json_schema["$defs"]["User"]["type"], "object");
+        assert_eq!(json_schema["$defs"]["User"]["required"], serde_json::json!(["name"]));
+        assert_eq!(json_schema["$defs"]["User"]["properties"]["name"]["type"], "string");
+        assert_eq!(json_schema["$defs"]["User"]["properties"]["name"]["minLength"], 1);
+        assert_eq!(
+            json_schema["$defs"]["User"]["properties"]["nickname"]["anyOf"][1]["type"],
+            "null"
+        );
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn test_json_schema_round_trips_through_export_and_import() {
+        let mut schema = Schema::new();
+        schema.add_enum("Status".to_string(), vec!["active".to_string(), "inactive".to_string()]);
+
+        let mut fields = HashMap::new();
+        fields.insert(
+            "name".to_string(),
+            FieldDefinition::new(TypeDefinition::String, true).with_constraint(Constraint::MinLength(1)),
+        );
+        fields.insert("status".to_string(), FieldDefinition::new(TypeDefinition::Enum("Status".to_string()), true));
+        schema.add_object_schema("User".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("User".to_string()));
+
+        let json_schema = schema.to_json_schema();
+        let imported = Schema::from_json_schema(&json_schema).unwrap();
+
+        assert_eq!(imported.enum_defs.get("Status").unwrap().len(), 2);
+        let user_fields = imported.object_schemas.get("User").unwrap();
+        assert!(user_fields.get("name").unwrap().required);
+        assert_eq!(user_fields.get("name").unwrap().constraints.len(), 1);
+        assert_eq!(imported.root_schema, Some(TypeDefinition::Object("User".to_string())));
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn test_from_json_schema_imports_plain_json_schema_document() {
+        let document = serde_json::json!({
+            "$defs": {
+                "Role": {
+                    "type": "string",
+                    "enum": ["admin", "member"]
+                }
+            },
+        });
+
+        // A document with no "type"/"$ref"/"anyOf" at its root (just `$defs`)
+        // has no root schema to import - exercise that alongside a document
+        // whose root is a plain primitive type.
+        let primitive_document = serde_json::json!({"type": "integer"});
+        let imported = Schema::from_json_schema(&primitive_document).unwrap();
+        assert_eq!(imported.root_schema, Some(TypeDefinition::Integer));
+
+        let imported_with_defs = Schema::from_json_schema(&document).unwrap();
+        assert_eq!(imported_with_defs.enum_defs.get("Role").unwrap(), &vec!["admin".to_string(), "member".to_string()]);
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn test_json_schema_round_trips_scalar_default_value() {
+        let mut schema = Schema::new();
+        let mut fields = HashMap::new();
+        fields.insert(
+            "retries".to_string(),
+            FieldDefinition::new(TypeDefinition::Integer, false).with_default(CfgppValue::Integer(3)),
+        );
+        schema.add_object_schema("Policy".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("Policy".to_string()));
+
+        let json_schema = schema.to_json_schema();
+        assert_eq!(json_schema["$defs"]["Policy"]["properties"]["retries"]["default"], 3);
+
+        let imported = Schema::from_json_schema(&json_schema).unwrap();
+        let retries = imported.object_schemas.get("Policy").unwrap().get("retries").unwrap();
+        assert_eq!(retries.default_value, Some(CfgppValue::Integer(3)));
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn test_json_schema_round_trips_field_aliases() {
+        let mut schema = Schema::new();
+        let mut fields = HashMap::new();
+        fields.insert(
+            "email".to_string(),
+            FieldDefinition::new(TypeDefinition::String, true).with_alias("email_address"),
+        );
+        schema.add_object_schema("User".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("User".to_string()));
+
+        let json_schema = schema.to_json_schema();
+        assert_eq!(json_schema["$defs"]["User"]["properties"]["email"]["x-cfgpp-aliases"], serde_json::json!(["email_address"]));
+
+        let imported = Schema::from_json_schema(&json_schema).unwrap();
+        let email = imported.object_schemas.get("User").unwrap().get("email").unwrap();
+        assert_eq!(email.aliases, vec!["email_address".to_string()]);
+    }
+
+    #[test]
+    fn test_validate_errors_carry_no_span_by_default() {
+        let mut schema = Schema::new();
+        let mut fields = HashMap::new();
+        fields.insert("name".to_string(), FieldDefinition::new(TypeDefinition::String, true));
+        schema.add_object_schema("User".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("User".to_string()));
+
+        let errors = schema.validate(&CfgppValue::object()).unwrap_err();
+        assert_eq!(errors[0].span, None);
+        assert_eq!(errors[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_render_with_span_underlines_the_offending_text() {
+        let source = "port = 99999;\n";
+        let error = ValidationDiagnostic {
+            path: JsonPath::opaque("port"),
+            code: ErrorCode::ConstraintViolation,
+            message: "value 99999 exceeds maximum 65535".to_string(),
+            expected_type: None,
+            actual_type: None,
+            span: Some((7, 12)),
+            severity: Severity::Error,
+        };
+
+        let rendered = error.render(source);
+
+        assert!(rendered.contains("error: value 99999 exceeds maximum 65535"));
+        assert!(rendered.contains("port (line 1, column 8)"));
+        assert!(rendered.contains("port = 99999;"));
+        assert!(rendered.contains("^^^^^"));
+    }
+
+    #[test]
+    fn test_render_aligns_carets_past_multibyte_characters() {
+        // "café" is 4 chars but 5 bytes ('é' is 2 bytes in UTF-8), so the
+        // byte offset of "99999" (8) is one past its character column (7).
+        let source = "café = 99999;\n";
+        let error = ValidationDiagnostic {
+            path: JsonPath::opaque("cafe"),
+            code: ErrorCode::ConstraintViolation,
+            message: "value 99999 exceeds maximum 65535".to_string(),
+            expected_type: None,
+            actual_type: None,
+            span: Some((8, 13)),
+            severity: Severity::Error,
+        };
+
+        let rendered = error.render(source);
+        let lines: Vec<&str> = rendered.lines().collect();
+        let text_line = lines.iter().position(|l| l.contains("café")).unwrap();
+        let caret_line = lines[text_line + 1];
+
+        // Compare character columns, not byte offsets - "é" is 2 bytes but
+        // a single printed column, so a byte-offset comparison would fail
+        // here even when the carets are visually aligned correctly.
+        let text_byte_offset = lines[text_line].find("99999").unwrap();
+        let text_column = lines[text_line][..text_byte_offset].chars().count();
+        let caret_column = caret_line.find('^').unwrap(); // ASCII-only line: byte offset == char column
+        assert_eq!(text_column, caret_column);
+    }
+
+    #[test]
+    fn test_render_without_span_falls_back_to_display() {
+        let error = ValidationDiagnostic {
+            path: JsonPath::opaque("port"),
+            code: ErrorCode::ConstraintViolation,
+            message: "value 99999 exceeds maximum 65535".to_string(),
+            expected_type: None,
+            actual_type: None,
+            span: None,
+            severity: Severity::Error,
+        };
+
+        assert_eq!(error.render("irrelevant source"), format!("error: {}", error));
+    }
+
+    fn field(field_type: TypeDefinition, required: bool) -> FieldDefinition {
+        FieldDefinition::new(field_type, required)
+    }
+
+    #[test]
+    fn test_compatibility_new_required_field_without_default_is_forward_only() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("name".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("name".to_string(), field(TypeDefinition::String, true));
+            fields.insert("email".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(!report.has_breaking_changes());
+        assert!(!report.is_backward_compatible());
+        assert!(report.is_forward_compatible());
+        assert!(report.changes.iter().any(|c| c.path == "User.email" && c.kind == CompatibilityKind::ForwardOnly));
+    }
+
+    #[test]
+    fn test_compatibility_new_optional_field_is_compatible() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("name".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("name".to_string(), field(TypeDefinition::String, true));
+            fields.insert("nickname".to_string(), field(TypeDefinition::String, false));
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(!report.has_breaking_changes());
+        assert!(report.is_backward_compatible());
+        assert!(report.is_forward_compatible());
+        assert!(report
+            .changes
+            .iter()
+            .any(|c| c.path == "User.nickname" && c.kind == CompatibilityKind::Compatible));
+    }
+
+    #[test]
+    fn test_compatibility_removed_required_field_is_backward_only() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("name".to_string(), field(TypeDefinition::String, true));
+            fields.insert("legacy_id".to_string(), field(TypeDefinition::Integer, true));
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("name".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(!report.has_breaking_changes());
+        assert!(report.is_backward_compatible());
+        assert!(!report.is_forward_compatible());
+        assert!(report
+            .changes
+            .iter()
+            .any(|c| c.path == "User.legacy_id" && c.kind == CompatibilityKind::BackwardOnly));
+    }
+
+    #[test]
+    fn test_compatibility_removed_optional_field_is_compatible() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("name".to_string(), field(TypeDefinition::String, true));
+            fields.insert("middle_name".to_string(), field(TypeDefinition::String, false));
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("name".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(!report.has_breaking_changes());
+        assert!(report.is_forward_compatible());
+        assert!(report.is_backward_compatible());
+        assert!(report
+            .changes
+            .iter()
+            .any(|c| c.path == "User.middle_name" && c.kind == CompatibilityKind::Compatible));
+    }
+
+    #[test]
+    fn test_compatibility_matches_renamed_field_via_alias() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("email".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "contact_email".to_string(),
+                field(TypeDefinition::String, true).with_alias("email"),
+            );
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(report.changes.is_empty(), "renamed field should match via alias, not read as add+remove: {:?}", report.changes);
+    }
+
+    #[test]
+    fn test_compatibility_integer_widened_to_double_is_backward_only() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("Reading".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("value".to_string(), field(TypeDefinition::Integer, true));
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("Reading".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("value".to_string(), field(TypeDefinition::Double, true));
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(!report.has_breaking_changes());
+        assert!(report.is_backward_compatible());
+        assert!(!report.is_forward_compatible());
+        assert_eq!(report.changes[0].kind, CompatibilityKind::BackwardOnly);
+    }
+
+    #[test]
+    fn test_compatibility_type_widened_to_union_containing_it_is_backward_only() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("Reading".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("value".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("Reading".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "value".to_string(),
+                field(TypeDefinition::Union(vec![TypeDefinition::String, TypeDefinition::Integer]), true),
+            );
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(!report.has_breaking_changes());
+        assert!(report.is_backward_compatible());
+        assert!(!report.is_forward_compatible());
+        assert_eq!(report.changes[0].kind, CompatibilityKind::BackwardOnly);
+    }
+
+    #[test]
+    fn test_compatibility_incompatible_type_change_is_breaking() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("Reading".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("value".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("Reading".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("value".to_string(), field(TypeDefinition::Boolean, true));
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(report.has_breaking_changes());
+    }
+
+    #[test]
+    fn test_compatibility_narrowed_max_value_constraint_is_forward_only() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("Reading".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "value".to_string(),
+                field(TypeDefinition::Integer, true).with_constraint(Constraint::MaxValue(100.0)),
+            );
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("Reading".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "value".to_string(),
+                field(TypeDefinition::Integer, true).with_constraint(Constraint::MaxValue(50.0)),
+            );
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(!report.has_breaking_changes());
+        assert!(report.is_forward_compatible());
+        assert!(!report.is_backward_compatible());
+        assert_eq!(report.changes[0].kind, CompatibilityKind::ForwardOnly);
+    }
+
+    #[test]
+    fn test_compatibility_widened_max_value_constraint_is_backward_only() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("Reading".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "value".to_string(),
+                field(TypeDefinition::Integer, true).with_constraint(Constraint::MaxValue(50.0)),
+            );
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("Reading".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "value".to_string(),
+                field(TypeDefinition::Integer, true).with_constraint(Constraint::MaxValue(100.0)),
+            );
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(!report.has_breaking_changes());
+        assert!(report.is_backward_compatible());
+        assert!(!report.is_forward_compatible());
+        assert_eq!(report.changes[0].kind, CompatibilityKind::BackwardOnly);
+    }
+
+    #[test]
+    fn test_compatibility_removed_enum_value_is_forward_only() {
+        let mut writer = Schema::new();
+        writer.add_enum("Status".to_string(), vec!["active".to_string(), "retired".to_string()]);
+
+        let mut reader = Schema::new();
+        reader.add_enum("Status".to_string(), vec!["active".to_string()]);
+
+        let report = reader.check_compatibility(&writer);
+        assert!(!report.has_breaking_changes());
+        assert!(report.is_forward_compatible());
+        assert!(!report.is_backward_compatible());
+        assert!(report.changes.iter().any(|c| c.path == "enum Status" && c.kind == CompatibilityKind::ForwardOnly));
+    }
+
+    #[test]
+    fn test_compatibility_added_enum_value_is_backward_only() {
+        let mut writer = Schema::new();
+        writer.add_enum("Status".to_string(), vec!["active".to_string()]);
+
+        let mut reader = Schema::new();
+        reader.add_enum("Status".to_string(), vec!["active".to_string(), "pending".to_string()]);
+
+        let report = reader.check_compatibility(&writer);
+        assert!(!report.has_breaking_changes());
+        assert!(report.is_backward_compatible());
+        assert!(!report.is_forward_compatible());
+    }
+
+    #[test]
+    fn test_compatibility_identical_schemas_produce_no_changes() {
+        let mut schema = Schema::new();
+        schema.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("name".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+        schema.add_enum("Status".to_string(), vec!["active".to_string()]);
+
+        let report = schema.check_compatibility(&schema);
+        assert!(report.changes.is_empty());
+    }
+
+    #[test]
+    fn test_compatibility_narrowed_max_length_constraint_is_forward_only() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "name".to_string(),
+                field(TypeDefinition::String, true).with_constraint(Constraint::MaxLength(100)),
+            );
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "name".to_string(),
+                field(TypeDefinition::String, true).with_constraint(Constraint::MaxLength(20)),
+            );
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(!report.has_breaking_changes());
+        assert!(report.is_forward_compatible());
+        assert!(!report.is_backward_compatible());
+        assert_eq!(report.changes[0].kind, CompatibilityKind::ForwardOnly);
+    }
+
+    #[test]
+    fn test_compatibility_widened_min_length_constraint_is_backward_only() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "name".to_string(),
+                field(TypeDefinition::String, true).with_constraint(Constraint::MinLength(5)),
+            );
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "name".to_string(),
+                field(TypeDefinition::String, true).with_constraint(Constraint::MinLength(1)),
+            );
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(!report.has_breaking_changes());
+        assert!(report.is_backward_compatible());
+        assert!(!report.is_forward_compatible());
+        assert_eq!(report.changes[0].kind, CompatibilityKind::BackwardOnly);
+    }
+
+    #[test]
+    fn test_compatibility_pattern_change_is_breaking() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "id".to_string(),
+                field(TypeDefinition::String, true).with_constraint(Constraint::Pattern(Regex::new("^[0-9]+$").unwrap())),
+            );
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "id".to_string(),
+                field(TypeDefinition::String, true).with_constraint(Constraint::Pattern(Regex::new("^[a-z0-9]+$").unwrap())),
+            );
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(report.has_breaking_changes());
+        assert_eq!(report.changes[0].kind, CompatibilityKind::Breaking);
+    }
+
+    #[test]
+    fn test_compatibility_field_made_required_without_default_is_forward_only() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("age".to_string(), field(TypeDefinition::Integer, false));
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("age".to_string(), field(TypeDefinition::Integer, true));
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(!report.has_breaking_changes());
+        assert!(!report.is_backward_compatible());
+        assert!(report.is_forward_compatible());
+        assert!(report.changes.iter().any(|c| c.path == "User.age" && c.kind == CompatibilityKind::ForwardOnly));
+    }
+
+    #[test]
+    fn test_compatibility_field_made_optional_is_not_flagged() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("age".to_string(), field(TypeDefinition::Integer, true));
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("age".to_string(), field(TypeDefinition::Integer, false));
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(report.changes.is_empty());
+    }
+
+    #[test]
+    fn test_compatibility_type_wrapped_in_optional_is_backward_only() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("nickname".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "nickname".to_string(),
+                field(TypeDefinition::Optional(Box::new(TypeDefinition::String)), true),
+            );
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(!report.has_breaking_changes());
+        assert!(report.is_backward_compatible());
+        assert!(!report.is_forward_compatible());
+        assert_eq!(report.changes[0].kind, CompatibilityKind::BackwardOnly);
+    }
+
+    #[test]
+    fn test_compatibility_removed_object_schema_is_breaking() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("Notification".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("message".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        let reader = Schema::new();
+
+        let report = reader.check_compatibility(&writer);
+        assert!(report.has_breaking_changes());
+        assert!(report
+            .changes
+            .iter()
+            .any(|c| c.path == "object Notification" && c.kind == CompatibilityKind::Breaking));
+    }
+
+    #[test]
+    fn test_compatibility_new_object_schema_is_compatible() {
+        let writer = Schema::new();
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("Notification".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("message".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(!report.has_breaking_changes());
+        assert!(report.is_backward_compatible());
+        assert!(report.is_forward_compatible());
+        assert!(report
+            .changes
+            .iter()
+            .any(|c| c.path == "object Notification" && c.kind == CompatibilityKind::Compatible));
+    }
+
+    #[test]
+    fn test_compatibility_removed_enum_is_breaking() {
+        let mut writer = Schema::new();
+        writer.add_enum("Status".to_string(), vec!["active".to_string()]);
+
+        let reader = Schema::new();
+
+        let report = reader.check_compatibility(&writer);
+        assert!(report.has_breaking_changes());
+        assert!(report
+            .changes
+            .iter()
+            .any(|c| c.path == "enum Status" && c.kind == CompatibilityKind::Breaking));
+    }
+
+    #[test]
+    fn test_compatibility_custom_validator_change_is_breaking() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "age".to_string(),
+                field(TypeDefinition::Integer, true).with_constraint(Constraint::Custom("even_only".to_string())),
+            );
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "age".to_string(),
+                field(TypeDefinition::Integer, true).with_constraint(Constraint::Custom("positive_only".to_string())),
+            );
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(report.has_breaking_changes());
+        assert_eq!(report.changes[0].kind, CompatibilityKind::Breaking);
+    }
+
+    #[test]
+    fn test_compatibility_field_own_name_wins_over_colliding_alias() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "aaa_email".to_string(),
+                field(TypeDefinition::Integer, true).with_alias("email"),
+            );
+            fields.insert("email".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "aaa_email".to_string(),
+                field(TypeDefinition::Integer, true).with_alias("email"),
+            );
+            fields.insert("email".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(report.changes.is_empty());
+    }
+
+    #[test]
+    fn test_compatibility_unwrapping_optional_reports_only_the_type_narrowing() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "nickname".to_string(),
+                field(TypeDefinition::Optional(Box::new(TypeDefinition::String)), true),
+            );
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("nickname".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert_eq!(report.changes.len(), 1);
+        assert_eq!(report.changes[0].kind, CompatibilityKind::ForwardOnly);
+    }
+
+    #[test]
+    fn test_compatibility_widened_array_element_type_is_backward_only() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("Reading".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("values".to_string(), field(TypeDefinition::Array(Box::new(TypeDefinition::Integer)), true));
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("Reading".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("values".to_string(), field(TypeDefinition::Array(Box::new(TypeDefinition::Double)), true));
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(!report.has_breaking_changes());
+        assert_eq!(report.changes[0].kind, CompatibilityKind::BackwardOnly);
+    }
+
+    #[test]
+    fn test_compatibility_union_with_reordered_members_is_not_flagged() {
+        let mut writer = Schema::new();
+        writer.add_object_schema("Reading".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "value".to_string(),
+                field(TypeDefinition::Union(vec![TypeDefinition::String, TypeDefinition::Integer]), true),
+            );
+            fields
+        });
+
+        let mut reader = Schema::new();
+        reader.add_object_schema("Reading".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "value".to_string(),
+                field(TypeDefinition::Union(vec![TypeDefinition::Integer, TypeDefinition::String]), true),
+            );
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(report.changes.is_empty());
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable_and_order_independent() {
+        let mut a = Schema::new();
+        a.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("name".to_string(), field(TypeDefinition::String, true));
+            fields.insert("age".to_string(), field(TypeDefinition::Integer, false));
+            fields
+        });
+        a.add_enum("Status".to_string(), vec!["active".to_string(), "inactive".to_string()]);
+
+        let mut b = Schema::new();
+        b.add_enum("Status".to_string(), vec!["inactive".to_string(), "active".to_string()]);
+        b.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("age".to_string(), field(TypeDefinition::Integer, false));
+            fields.insert("name".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        assert_eq!(a.fingerprint(), b.fingerprint());
+    }
+
+    #[test]
+    fn test_fingerprint_changes_when_a_field_changes() {
+        let mut a = Schema::new();
+        a.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("name".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        let mut b = Schema::new();
+        b.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("name".to_string(), field(TypeDefinition::String, false));
+            fields
+        });
+
+        assert_ne!(a.fingerprint(), b.fingerprint());
+    }
+
+    #[test]
+    fn test_format_constraint_accepts_valid_values() {
+        let schema = Schema::new();
+        let field_def = FieldDefinition::new(TypeDefinition::String, true)
+            .with_constraint(Constraint::Format(FormatKind::Email));
+
+        assert!(schema.validate_field(&CfgppValue::string("user@example.com"), &field_def, "email", &CfgppValue::Null).is_ok());
+    }
+
+    #[test]
+    fn test_format_constraint_rejects_invalid_email() {
+        let schema = Schema::new();
+        let field_def = FieldDefinition::new(TypeDefinition::String, true)
+            .with_constraint(Constraint::Format(FormatKind::Email));
+
+        let result = schema.validate_field(&CfgppValue::string("not-an-email"), &field_def, "email", &CfgppValue::Null);
+        let errors = result.unwrap_err();
+        assert!(errors[0].message.contains("format 'email'"));
+    }
+
+    #[test]
+    fn test_format_constraint_validates_ipv4_and_ipv6() {
+        let schema = Schema::new();
+        let ipv4_field = FieldDefinition::new(TypeDefinition::String, true)
+            .with_constraint(Constraint::Format(FormatKind::Ipv4));
+        let ipv6_field = FieldDefinition::new(TypeDefinition::String, true)
+            .with_constraint(Constraint::Format(FormatKind::Ipv6));
+
+        assert!(schema.validate_field(&CfgppValue::string("192.168.0.1"), &ipv4_field, "addr", &CfgppValue::Null).is_ok());
+        assert!(schema.validate_field(&CfgppValue::string("not-an-ip"), &ipv4_field, "addr", &CfgppValue::Null).is_err());
+        assert!(schema.validate_field(&CfgppValue::string("::1"), &ipv6_field, "addr", &CfgppValue::Null).is_ok());
+        assert!(schema.validate_field(&CfgppValue::string("192.168.0.1"), &ipv6_field, "addr", &CfgppValue::Null).is_err());
+    }
+
+    #[test]
+    fn test_format_constraint_validates_uuid_and_date_time() {
+        let schema = Schema::new();
+        let uuid_field = FieldDefinition::new(TypeDefinition::String, true)
+            .with_constraint(Constraint::Format(FormatKind::Uuid));
+        let date_time_field = FieldDefinition::new(TypeDefinition::String, true)
+            .with_constraint(Constraint::Format(FormatKind::DateTime));
+
+        assert!(schema.validate_field(&CfgppValue::string("550e8400-e29b-41d4-a716-446655440000"), &uuid_field, "id", &CfgppValue::Null).is_ok());
+        assert!(schema.validate_field(&CfgppValue::string("not-a-uuid"), &uuid_field, "id", &CfgppValue::Null).is_err());
+        assert!(schema.validate_field(&CfgppValue::string("2024-01-15T10:30:00Z"), &date_time_field, "ts", &CfgppValue::Null).is_ok());
+        assert!(schema.validate_field(&CfgppValue::string("2024-01-15"), &date_time_field, "ts", &CfgppValue::Null).is_err());
+    }
+
+    #[test]
+    fn test_check_flags_format_constraint_on_incompatible_type() {
+        let mut schema = Schema::new();
+        let mut fields = HashMap::new();
+        fields.insert(
+            "age".to_string(),
+            FieldDefinition::new(TypeDefinition::Integer, true).with_constraint(Constraint::Format(FormatKind::Email)),
+        );
+        schema.add_object_schema("User".to_string(), fields);
+
+        let errors = schema.check();
+        assert!(errors.iter().any(|e| e.message.contains("can never apply")));
+    }
+
+    #[test]
+    fn test_one_of_matches_exactly_one_branch() {
+        let mut schema = Schema::new();
+        schema.set_root_schema(TypeDefinition::OneOf(vec![
+            TypeDefinition::Object("Cat".to_string()),
+            TypeDefinition::Object("Dog".to_string()),
+        ]));
+
+        let mut cat_fields = HashMap::new();
+        cat_fields.insert("meow".to_string(), FieldDefinition::new(TypeDefinition::Boolean, true));
+        schema.add_object_schema("Cat".to_string(), cat_fields);
+
+        let mut dog_fields = HashMap::new();
+        dog_fields.insert("bark".to_string(), FieldDefinition::new(TypeDefinition::Boolean, true));
+        schema.add_object_schema("Dog".to_string(), dog_fields);
+
+        let mut cat_obj = crate::value::CfgppObject::new();
+        cat_obj.insert("meow".to_string(), CfgppValue::Boolean(true));
+        let cat_value = CfgppValue::object_with_values(cat_obj);
+
+        assert!(schema.validate(&cat_value).is_ok());
+    }
+
+    #[test]
+    fn test_one_of_rejects_value_matching_zero_branches() {
+        let mut schema = Schema::new();
+        schema.set_root_schema(TypeDefinition::OneOf(vec![TypeDefinition::String, TypeDefinition::Integer]));
+
+        let result = schema.validate(&CfgppValue::Boolean(true));
+        let errors = result.unwrap_err();
+        assert!(errors[0].message.contains("does not match any branch of oneOf"));
+    }
+
+    #[test]
+    fn test_one_of_rejects_value_matching_multiple_branches() {
+        let mut schema = Schema::new();
+        // Every object satisfies an all-optional-fields schema, so a value
+        // conforming to both branches should be rejected as ambiguous.
+        schema.set_root_schema(TypeDefinition::OneOf(vec![
+            TypeDefinition::Object("Empty".to_string()),
+            TypeDefinition::Object("AlsoEmpty".to_string()),
+        ]));
+        schema.add_object_schema("Empty".to_string(), HashMap::new());
+        schema.add_object_schema("AlsoEmpty".to_string(), HashMap::new());
+
+        let result = schema.validate(&CfgppValue::object_with_values(crate::value::CfgppObject::new()));
+        let errors = result.unwrap_err();
+        assert!(errors[0].message.contains("matches 2 branches of oneOf"));
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn test_json_schema_round_trips_format_constraint() {
+        let mut schema = Schema::new();
+        let mut fields = HashMap::new();
+        fields.insert(
+            "email".to_string(),
+            FieldDefinition::new(TypeDefinition::String, true).with_constraint(Constraint::Format(FormatKind::Email)),
+        );
+        schema.add_object_schema("User".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("User".to_string()));
+
+        let json_schema = schema.to_json_schema();
+        assert_eq!(json_schema["$defs"]["User"]["properties"]["email"]["format"], "email");
+
+        let imported = Schema::from_json_schema(&json_schema).unwrap();
+        let email = imported.object_schemas.get("User").unwrap().get("email").unwrap();
+        assert!(matches!(email.constraints.as_slice(), [Constraint::Format(FormatKind::Email)]));
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn test_json_schema_round_trips_one_of() {
+        let mut schema = Schema::new();
+        schema.set_root_schema(TypeDefinition::OneOf(vec![TypeDefinition::String, TypeDefinition::Integer]));
+
+        let json_schema = schema.to_json_schema();
+        assert!(json_schema["oneOf"].is_array());
+
+        let imported = Schema::from_json_schema(&json_schema).unwrap();
+        assert_eq!(
+            imported.root_schema,
+            Some(TypeDefinition::OneOf(vec![TypeDefinition::String, TypeDefinition::Integer]))
+        );
+    }
+
+    #[test]
+    fn test_compatibility_format_constraint_change_is_breaking() {
+        let mut reader = Schema::new();
+        reader.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "contact".to_string(),
+                field(TypeDefinition::String, true).with_constraint(Constraint::Format(FormatKind::Email)),
+            );
+            fields
+        });
+
+        let mut writer = Schema::new();
+        writer.add_object_schema("User".to_string(), {
+            let mut fields = HashMap::new();
+            fields.insert("contact".to_string(), field(TypeDefinition::String, true));
+            fields
+        });
+
+        let report = reader.check_compatibility(&writer);
+        assert!(report.changes.iter().any(|c| c.kind == CompatibilityKind::Breaking && c.path == "User.contact"));
+    }
+
+    fn tls_config_schema() -> Schema {
+        let mut schema = Schema::new();
+        let mut fields = HashMap::new();
+        fields.insert("tls_enabled".to_string(), field(TypeDefinition::Boolean, true));
+        fields.insert(
+            "cert_path".to_string(),
+            field(TypeDefinition::String, true)
+                .when(FieldCondition::Eq("tls_enabled".to_string(), CfgppValue::Boolean(true))),
+        );
+        schema.add_object_schema("TlsConfig".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("TlsConfig".to_string()));
+        schema
+    }
+
+    #[test]
+    fn test_guarded_field_not_required_when_condition_is_false() {
+        let schema = tls_config_schema();
+        let mut obj = crate::value::CfgppObject::new();
+        obj.insert("tls_enabled".to_string(), CfgppValue::Boolean(false));
+
+        assert!(schema.validate(&CfgppValue::object_with_values(obj)).is_ok());
+    }
+
+    #[test]
+    fn test_guarded_field_required_when_condition_is_true() {
+        let schema = tls_config_schema();
+        let mut obj = crate::value::CfgppObject::new();
+        obj.insert("tls_enabled".to_string(), CfgppValue::Boolean(true));
+
+        let errors = schema.validate(&CfgppValue::object_with_values(obj)).unwrap_err();
+        assert!(errors.iter().any(|e| e.path == "$.cert_path" && e.message.contains("missing")));
+    }
+
+    #[test]
+    fn test_guarded_off_field_present_is_an_error() {
+        let schema = tls_config_schema();
+        let mut obj = crate::value::CfgppObject::new();
+        obj.insert("tls_enabled".to_string(), CfgppValue::Boolean(false));
+        obj.insert("cert_path".to_string(), CfgppValue::string("/etc/tls/cert.pem"));
+
+        let errors = schema.validate(&CfgppValue::object_with_values(obj)).unwrap_err();
+        assert!(errors.iter().any(|e| e.path == "$.cert_path" && e.message.contains("guard")));
+    }
+
+    #[test]
+    fn test_guard_field_path_resolves_into_nested_object() {
+        let mut schema = Schema::new();
+        let mut tls_fields = HashMap::new();
+        tls_fields.insert("enabled".to_string(), field(TypeDefinition::Boolean, true));
+        schema.add_object_schema("Tls".to_string(), tls_fields);
+
+        let mut fields = HashMap::new();
+        fields.insert("tls".to_string(), field(TypeDefinition::Object("Tls".to_string()), true));
+        fields.insert(
+            "cert_path".to_string(),
+            field(TypeDefinition::String, true)
+                .when(FieldCondition::Eq("tls.enabled".to_string(), CfgppValue::Boolean(true))),
+        );
+        schema.add_object_schema("Server".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("Server".to_string()));
+
+        let mut tls_obj = crate::value::CfgppObject::new();
+        tls_obj.insert("enabled".to_string(), CfgppValue::Boolean(false));
+        let mut obj = crate::value::CfgppObject::new();
+        obj.insert("tls".to_string(), CfgppValue::object_with_values(tls_obj));
+
+        assert!(schema.validate(&CfgppValue::object_with_values(obj)).is_ok());
+    }
+
+    #[test]
+    fn test_guard_missing_field_path_is_treated_as_false() {
+        let mut schema = Schema::new();
+        let mut fields = HashMap::new();
+        fields.insert(
+            "backup_path".to_string(),
+            field(TypeDefinition::String, true)
+                .when(FieldCondition::Exists("backup_enabled".to_string())),
+        );
+        schema.add_object_schema("Config".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("Config".to_string()));
+
+        let obj = crate::value::CfgppObject::new();
+        assert!(schema.validate(&CfgppValue::object_with_values(obj)).is_ok());
+    }
+
+    #[test]
+    fn test_guard_and_or_not_combinators() {
+        let condition = FieldCondition::And(
+            Box::new(FieldCondition::Eq("mode".to_string(), CfgppValue::string("advanced"))),
+            Box::new(FieldCondition::Not(Box::new(FieldCondition::Exists("legacy".to_string())))),
+        );
+
+        let mut satisfied = crate::value::CfgppObject::new();
+        satisfied.insert("mode".to_string(), CfgppValue::string("advanced"));
+        assert!(Schema::eval_condition(&condition, &satisfied));
+
+        satisfied.insert("legacy".to_string(), CfgppValue::Boolean(true));
+        assert!(!Schema::eval_condition(&condition, &satisfied));
+
+        let or_condition = FieldCondition::Or(
+            Box::new(FieldCondition::Eq("mode".to_string(), CfgppValue::string("advanced"))),
+            Box::new(FieldCondition::Eq("mode".to_string(), CfgppValue::string("expert"))),
+        );
+        let mut expert = crate::value::CfgppObject::new();
+        expert.insert("mode".to_string(), CfgppValue::string("expert"));
+        assert!(Schema::eval_condition(&or_condition, &expert));
+    }
+
+    #[test]
+    fn test_cpf_format_accepts_valid_number_with_punctuation() {
+        let schema = Schema::new();
+        let field_def = FieldDefinition::new(TypeDefinition::String, true)
+            .with_constraint(Constraint::NamedFormat("cpf".to_string()));
+
+        assert!(schema.validate_field(&CfgppValue::string("111.444.777-35"), &field_def, "cpf", &CfgppValue::Null).is_ok());
+    }
+
+    #[test]
+    fn test_cpf_format_rejects_wrong_check_digit() {
+        let schema = Schema::new();
+        let field_def = FieldDefinition::new(TypeDefinition::String, true)
+            .with_constraint(Constraint::NamedFormat("cpf".to_string()));
+
+        let errors = schema.validate_field(&CfgppValue::string("111.444.777-36"), &field_def, "cpf", &CfgppValue::Null).unwrap_err();
+        assert!(errors[0].message.contains("check digit"));
+    }
+
+    #[test]
+    fn test_cpf_format_rejects_all_identical_digits() {
+        let schema = Schema::new();
+        let field_def = FieldDefinition::new(TypeDefinition::String, true)
+            .with_constraint(Constraint::NamedFormat("cpf".to_string()));
+
+        assert!(schema.validate_field(&CfgppValue::string("111.111.111-11"), &field_def, "cpf", &CfgppValue::Null).is_err());
+    }
+
+    #[test]
+    fn test_cnpj_format_accepts_valid_number_with_punctuation() {
+        let schema = Schema::new();
+        let field_def = FieldDefinition::new(TypeDefinition::String, true)
+            .with_constraint(Constraint::NamedFormat("cnpj".to_string()));
+
+        assert!(schema.validate_field(&CfgppValue::string("11.222.333/0001-81"), &field_def, "cnpj", &CfgppValue::Null).is_ok());
+    }
+
+    #[test]
+    fn test_cnpj_format_rejects_wrong_check_digit() {
+        let schema = Schema::new();
+        let field_def = FieldDefinition::new(TypeDefinition::String, true)
+            .with_constraint(Constraint::NamedFormat("cnpj".to_string()));
+
+        let errors = schema.validate_field(&CfgppValue::string("11.222.333/0001-80"), &field_def, "cnpj", &CfgppValue::Null).unwrap_err();
+        assert!(errors[0].message.contains("check digit"));
+    }
+
+    #[test]
+    fn test_named_format_reports_unregistered_validator() {
+        let schema = Schema::new();
+        let field_def = FieldDefinition::new(TypeDefinition::String, true)
+            .with_constraint(Constraint::NamedFormat("ssn".to_string()));
+
+        let errors = schema.validate_field(&CfgppValue::string("anything"), &field_def, "ssn", &CfgppValue::Null).unwrap_err();
+        assert!(errors[0].message.contains("No format validator registered"));
+    }
+
+    #[test]
+    fn test_custom_named_format_validator_can_be_registered() {
+        struct AlwaysEven;
+        impl FormatValidator for AlwaysEven {
+            fn validate(&self, value: &str) -> Result<(), String> {
+                match value.parse::<u32>() {
+                    Ok(n) if n % 2 == 0 => Ok(()),
+                    _ => Err("value must be an even number".to_string()),
+                }
+            }
+        }
+
+        let mut schema = Schema::new();
+        schema.register_format_validator("even_number", AlwaysEven);
+        let field_def = FieldDefinition::new(TypeDefinition::String, true)
+            .with_constraint(Constraint::NamedFormat("even_number".to_string()));
+
+        assert!(schema.validate_field(&CfgppValue::string("4"), &field_def, "n", &CfgppValue::Null).is_ok());
+        assert!(schema.validate_field(&CfgppValue::string("3"), &field_def, "n", &CfgppValue::Null).is_err());
+    }
+
+    #[test]
+    fn test_check_flags_unregistered_named_format() {
+        let mut schema = Schema::new();
+        let mut fields = HashMap::new();
+        fields.insert(
+            "ssn".to_string(),
+            FieldDefinition::new(TypeDefinition::String, true).with_constraint(Constraint::NamedFormat("ssn".to_string())),
+        );
+        schema.add_object_schema("Person".to_string(), fields);
+
+        let errors = schema.check();
+        assert!(errors.iter().any(|e| e.message.contains("No format validator registered for 'ssn'")));
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn test_json_schema_round_trips_named_format() {
+        let mut schema = Schema::new();
+        let mut fields = HashMap::new();
+        fields.insert(
+            "taxpayer_id".to_string(),
+            FieldDefinition::new(TypeDefinition::String, true).with_constraint(Constraint::NamedFormat("cpf".to_string())),
+        );
+        schema.add_object_schema("Person".to_string(), fields);
+
+        let json_schema = schema.to_json_schema();
+        assert_eq!(json_schema["$defs"]["Person"]["properties"]["taxpayer_id"]["format"], "cpf");
+
+        let imported = Schema::from_json_schema(&json_schema).unwrap();
+        let field = &imported.object_schemas["Person"]["taxpayer_id"];
+        assert!(matches!(field.constraints.as_slice(), [Constraint::NamedFormat(name)] if name == "cpf"));
+    }
+
+    fn point_tuple_schema() -> Schema {
+        let mut schema = Schema::new();
+        schema.add_tuple_schema(
+            "Point".to_string(),
+            vec![
+                FieldDefinition::new(TypeDefinition::Integer, true),
+                FieldDefinition::new(TypeDefinition::Integer, true),
+                FieldDefinition::new(TypeDefinition::String, false),
+            ],
+        );
+        schema.set_root_schema(TypeDefinition::Tuple("Point".to_string()));
+        schema
+    }
+
+    #[test]
+    fn test_tuple_schema_accepts_values_for_each_slot() {
+        let schema = point_tuple_schema();
+        let value = CfgppValue::Array(vec![CfgppValue::Integer(1), CfgppValue::Integer(2)]);
+        assert!(schema.validate(&value).is_ok());
+    }
+
+    #[test]
+    fn test_tuple_schema_accepts_optional_trailing_slot() {
+        let schema = point_tuple_schema();
+        let value =
+            CfgppValue::Array(vec![CfgppValue::Integer(1), CfgppValue::Integer(2), CfgppValue::string("label")]);
+        assert!(schema.validate(&value).is_ok());
+    }
+
+    #[test]
+    fn test_tuple_schema_rejects_missing_required_slot() {
+        let schema = point_tuple_schema();
+        let value = CfgppValue::Array(vec![CfgppValue::Integer(1)]);
+        let errors = schema.validate(&value).unwrap_err();
+        assert!(errors.iter().any(|e| e.path == "$[1]" && e.message.contains("Missing field at index 1")));
+    }
+
+    #[test]
+    fn test_tuple_schema_rejects_extra_elements() {
+        let schema = point_tuple_schema();
+        let value = CfgppValue::Array(vec![
+            CfgppValue::Integer(1),
+            CfgppValue::Integer(2),
+            CfgppValue::string("label"),
+            CfgppValue::Boolean(true),
+        ]);
+        let errors = schema.validate(&value).unwrap_err();
+        assert!(errors.iter().any(|e| e.path == "$[3]" && e.message.contains("Struct has no field at index 3")));
+    }
+
+    #[test]
+    fn test_tuple_schema_reports_wrong_slot_type() {
+        let schema = point_tuple_schema();
+        let value = CfgppValue::Array(vec![CfgppValue::string("not an int"), CfgppValue::Integer(2)]);
+        assert!(schema.validate(&value).is_err());
+    }
+
+    #[test]
+    fn test_tuple_schema_unknown_name_is_an_error() {
+        let schema = Schema::new();
+        let value = CfgppValue::Array(vec![CfgppValue::Integer(1)]);
+        let errors = schema
+            .validate_field(&value, &FieldDefinition::new(TypeDefinition::Tuple("Missing".to_string()), true), "pos", &value)
+            .unwrap_err();
+        assert!(errors.iter().any(|e| e.message.contains("Unknown tuple schema 'Missing'")));
+    }
+
+    #[test]
+    fn test_check_flags_dangling_tuple_schema_reference() {
+        let mut schema = Schema::new();
+        schema.set_root_schema(TypeDefinition::Tuple("Missing".to_string()));
+        let errors = schema.check();
+        assert!(errors.iter().any(|e| e.message.contains("References undefined tuple schema 'Missing'")));
+    }
+
+    #[test]
+    fn test_json_schema_round_trips_tuple_schema() {
+        let schema = point_tuple_schema();
+        let json_schema = schema.to_json_schema();
+        assert_eq!(json_schema["$defs"]["Point"]["minItems"], 2);
+        assert_eq!(json_schema["$defs"]["Point"]["maxItems"], 3);
+
+        let imported = Schema::from_json_schema(&json_schema).unwrap();
+        let slots = &imported.tuple_schemas["Point"];
+        assert_eq!(slots.len(), 3);
+        assert!(slots[0].required);
+        assert!(!slots[2].required);
+        assert_eq!(imported.root_schema, Some(TypeDefinition::Tuple("Point".to_string())));
     }
 }
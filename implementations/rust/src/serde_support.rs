@@ -1,9 +1,15 @@
 //! Serde integration for CFG++ values
 
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use crate::value::CfgppValue;
-use std::collections::HashMap;
+use std::fmt;
+
+/// Newtype struct name `CfgppValue::Raw`'s `Serialize` impl tags its text
+/// with, so `CfgppSerializer` (the `to_cfgpp`/`to_cfgpp_pretty` backend) can
+/// recognize and emit it verbatim instead of re-serializing it.
+#[cfg(feature = "serde")]
+const CFGPP_RAW_MARKER: &str = "$cfgpp::RawValue";
 
 #[cfg(feature = "serde")]
 impl Serialize for CfgppValue {
@@ -15,10 +21,26 @@ impl Serialize for CfgppValue {
             CfgppValue::Null => serializer.serialize_none(),
             CfgppValue::Boolean(b) => serializer.serialize_bool(*b),
             CfgppValue::Integer(i) => serializer.serialize_i64(*i),
+            CfgppValue::UInteger(u) => serializer.serialize_u64(*u),
+            // Width/signedness metadata doesn't survive a generic `Serializer`
+            // (no format here has a "u8" scalar type); serialize the raw value
+            // and let a round-trip back through `Deserialize` land it as a
+            // plain `Integer`/`UInteger`, same as any other whole number.
+            CfgppValue::SizedInteger { value, signed, .. } if *signed => {
+                serializer.serialize_i64(*value)
+            }
+            CfgppValue::SizedInteger { value, .. } => serializer.serialize_u64(*value as u64),
+            #[cfg(feature = "arbitrary_precision")]
+            CfgppValue::BigNumber(digits) => serializer.serialize_str(digits),
             CfgppValue::Double(d) => serializer.serialize_f64(*d),
             CfgppValue::String(s) | CfgppValue::Enum(s) => serializer.serialize_str(s),
             CfgppValue::Array(arr) => arr.serialize(serializer),
             CfgppValue::Object(obj) => obj.serialize(serializer),
+            // Tagged with a marker name `CfgppSerializer::serialize_newtype_struct`
+            // recognizes to emit the text verbatim; every other `Serializer`
+            // (serde_json, toml, serde_yaml) ignores the unfamiliar name and
+            // falls back to serializing the text as an ordinary string.
+            CfgppValue::Raw(text) => serializer.serialize_newtype_struct(CFGPP_RAW_MARKER, text),
         }
     }
 }
@@ -101,10 +123,35 @@ impl<'de> Deserialize<'de> for CfgppValue {
             where
                 E: de::Error,
             {
-                if value <= i64::MAX as u64 {
-                    Ok(CfgppValue::Integer(value as i64))
+                match i64::try_from(value) {
+                    Ok(i) => Ok(CfgppValue::Integer(i)),
+                    Err(_) => Ok(CfgppValue::UInteger(value)),
+                }
+            }
+
+            fn visit_i128<E>(self, value: i128) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                if let Ok(i) = i64::try_from(value) {
+                    Ok(CfgppValue::Integer(i))
+                } else if let Ok(u) = u64::try_from(value) {
+                    Ok(CfgppValue::UInteger(u))
                 } else {
-                    Ok(CfgppValue::Double(value as f64))
+                    Err(de::Error::custom(format!("integer {} out of range for CfgppValue", value)))
+                }
+            }
+
+            fn visit_u128<E>(self, value: u128) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                if let Ok(i) = i64::try_from(value) {
+                    Ok(CfgppValue::Integer(i))
+                } else if let Ok(u) = u64::try_from(value) {
+                    Ok(CfgppValue::UInteger(u))
+                } else {
+                    Err(de::Error::custom(format!("integer {} out of range for CfgppValue", value)))
                 }
             }
 
@@ -174,7 +221,7 @@ impl<'de> Deserialize<'de> for CfgppValue {
             where
                 A: MapAccess<'de>,
             {
-                let mut object = HashMap::new();
+                let mut object = crate::value::CfgppObject::new();
 
                 while let Some((key, value)) = map.next_entry()? {
                     object.insert(key, value);
@@ -188,6 +235,1013 @@ impl<'de> Deserialize<'de> for CfgppValue {
     }
 }
 
+#[cfg(feature = "serde")]
+impl de::Error for crate::error::CfgppError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        crate::error::CfgppError::parse_error(msg.to_string())
+    }
+}
+
+/// Deserialize a `T` from a borrowed `CfgppValue`, without going through an
+/// intermediate JSON string.
+///
+/// This does not borrow string data out of `value` (every `CfgppValue`
+/// string is copied into the target type); true zero-copy deserialization
+/// needs a borrowed value model, which is tracked separately.
+#[cfg(feature = "serde")]
+pub fn deserialize_from<'de, T>(value: &CfgppValue) -> crate::error::CfgppResult<T>
+where
+    T: Deserialize<'de>,
+{
+    T::deserialize(value)
+}
+
+/// Deserialize a `T: DeserializeOwned` from a `CfgppValue`, e.g. a parsed
+/// config straight into a user's `#[derive(Deserialize)]` struct.
+#[cfg(feature = "serde")]
+pub fn from_value<T>(value: &CfgppValue) -> crate::error::CfgppResult<T>
+where
+    T: de::DeserializeOwned,
+{
+    T::deserialize(value)
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'a> Deserializer<'de> for &'a CfgppValue {
+    type Error = crate::error::CfgppError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            CfgppValue::Null => visitor.visit_unit(),
+            CfgppValue::Boolean(b) => visitor.visit_bool(*b),
+            CfgppValue::Integer(i) => visitor.visit_i64(*i),
+            CfgppValue::UInteger(u) => visitor.visit_u64(*u),
+            CfgppValue::SizedInteger { value, signed: true, .. } => visitor.visit_i64(*value),
+            CfgppValue::SizedInteger { value, signed: false, .. } => visitor.visit_u64(*value as u64),
+            #[cfg(feature = "arbitrary_precision")]
+            CfgppValue::BigNumber(digits) => visitor.visit_str(digits),
+            CfgppValue::Double(d) => visitor.visit_f64(*d),
+            CfgppValue::String(s) | CfgppValue::Enum(s) => visitor.visit_str(s),
+            CfgppValue::Array(arr) => visitor.visit_seq(CfgppSeqAccess { iter: arr.iter() }),
+            CfgppValue::Object(obj) => visitor.visit_map(CfgppMapAccess {
+                iter: Box::new(obj.iter()),
+                value: None,
+            }),
+            CfgppValue::Raw(_) => {
+                let forced = self.force()?;
+                (&forced).deserialize_any(visitor)
+            }
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            CfgppValue::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            CfgppValue::String(s) | CfgppValue::Enum(s) => visitor.visit_enum(CfgppEnumAccess {
+                variant: s,
+                value: None,
+            }),
+            CfgppValue::Object(obj) if obj.len() == 1 => {
+                let (variant, value) = obj.iter().next().unwrap();
+                visitor.visit_enum(CfgppEnumAccess {
+                    variant,
+                    value: Some(value),
+                })
+            }
+            other => Err(crate::error::CfgppError::type_error(
+                "enum (a string, or a single-key object for tuple/struct variants)",
+                other.type_name(),
+            )),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple tuple_struct
+        map struct identifier ignored_any
+    }
+}
+
+#[cfg(feature = "serde")]
+struct CfgppSeqAccess<'a> {
+    iter: std::slice::Iter<'a, CfgppValue>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'a> de::SeqAccess<'de> for CfgppSeqAccess<'a> {
+    type Error = crate::error::CfgppError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some(value) => seed.deserialize(value).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+struct CfgppMapAccess<'a> {
+    /// Boxed so this works unchanged whether `CfgppObject` is a `HashMap` or,
+    /// with `preserve_order` enabled, an `IndexMap`.
+    iter: Box<dyn Iterator<Item = (&'a String, &'a CfgppValue)> + 'a>,
+    value: Option<&'a CfgppValue>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'a> de::MapAccess<'de> for CfgppMapAccess<'a> {
+    type Error = crate::error::CfgppError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(de::value::StrDeserializer::new(key.as_str())).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let value = self
+            .value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct CfgppEnumAccess<'a> {
+    variant: &'a str,
+    value: Option<&'a CfgppValue>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'a> de::EnumAccess<'de> for CfgppEnumAccess<'a> {
+    type Error = crate::error::CfgppError;
+    type Variant = CfgppVariantAccess<'a>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant =
+            seed.deserialize(de::value::StrDeserializer::<crate::error::CfgppError>::new(self.variant))?;
+        Ok((variant, CfgppVariantAccess { value: self.value }))
+    }
+}
+
+#[cfg(feature = "serde")]
+struct CfgppVariantAccess<'a> {
+    value: Option<&'a CfgppValue>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'a> de::VariantAccess<'de> for CfgppVariantAccess<'a> {
+    type Error = crate::error::CfgppError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.value {
+            None => Ok(()),
+            Some(value) => Err(crate::error::CfgppError::type_error("unit variant", value.type_name())),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.value {
+            Some(value) => seed.deserialize(value),
+            None => Err(crate::error::CfgppError::type_error("newtype variant", "unit")),
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            Some(value @ CfgppValue::Array(_)) => value.deserialize_seq(visitor),
+            Some(value) => Err(crate::error::CfgppError::type_error("tuple variant", value.type_name())),
+            None => Err(crate::error::CfgppError::type_error("tuple variant", "unit")),
+        }
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            Some(value @ CfgppValue::Object(_)) => value.deserialize_map(visitor),
+            Some(value) => Err(crate::error::CfgppError::type_error("struct variant", value.type_name())),
+            None => Err(crate::error::CfgppError::type_error("struct variant", "unit")),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Error for crate::error::CfgppError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        crate::error::CfgppError::parse_error(msg.to_string())
+    }
+}
+
+/// A serialized fragment, built up by [`CfgppSerializer`] before being
+/// rendered to CFG++ text. Keeps object field order as encountered, since the
+/// textual renderer needs a stable order to produce readable output.
+#[cfg(feature = "serde")]
+enum CfgppNode {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Str(String),
+    /// A unit enum variant, rendered as a bare identifier rather than a
+    /// quoted string, matching how the parser treats bare identifiers as
+    /// `CfgppValue::Enum`.
+    EnumIdent(String),
+    Array(Vec<CfgppNode>),
+    Object(Vec<(String, CfgppNode)>),
+    /// Text carried over from a `CfgppValue::Raw` block, emitted byte-for-byte
+    /// with no quoting or re-serialization.
+    RawText(String),
+}
+
+/// `serde::Serializer` that renders any `Serialize` type as CFG++ text:
+/// objects as `key { ... }` blocks, scalar assignments as `key = value;`,
+/// arrays as `[a, b, c]`, and externally-tagged enum variants as a bare
+/// identifier (unit variants) or a single-key object (tuple/struct
+/// variants) - the inverse of `CfgppValue`'s `Deserializer` impl above.
+#[cfg(feature = "serde")]
+struct CfgppSerializer;
+
+#[cfg(feature = "serde")]
+impl Serializer for CfgppSerializer {
+    type Ok = CfgppNode;
+    type Error = crate::error::CfgppError;
+
+    type SerializeSeq = CfgppSeqSerializer;
+    type SerializeTuple = CfgppSeqSerializer;
+    type SerializeTupleStruct = CfgppSeqSerializer;
+    type SerializeTupleVariant = CfgppVariantSeqSerializer;
+    type SerializeMap = CfgppMapSerializer;
+    type SerializeStruct = CfgppMapSerializer;
+    type SerializeStructVariant = CfgppVariantMapSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(CfgppNode::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        Ok(CfgppNode::Int(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        match i64::try_from(v) {
+            Ok(i) => self.serialize_i64(i),
+            Err(_) => Ok(CfgppNode::UInt(v)),
+        }
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(CfgppNode::Float(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(&v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(CfgppNode::Str(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(CfgppNode::Array(v.iter().map(|b| CfgppNode::Int(*b as i64)).collect()))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(CfgppNode::Null)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(CfgppNode::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(CfgppNode::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(CfgppNode::EnumIdent(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        if name == CFGPP_RAW_MARKER {
+            return Ok(CfgppNode::RawText(value.serialize(CfgppRawTextSerializer)?));
+        }
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let inner = value.serialize(CfgppSerializer)?;
+        Ok(CfgppNode::Object(vec![(variant.to_string(), inner)]))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(CfgppSeqSerializer {
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Ok(CfgppVariantSeqSerializer {
+            variant,
+            items: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(CfgppMapSerializer {
+            entries: Vec::new(),
+            next_key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(CfgppMapSerializer {
+            entries: Vec::with_capacity(len),
+            next_key: None,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Ok(CfgppVariantMapSerializer {
+            variant,
+            entries: Vec::with_capacity(len),
+        })
+    }
+}
+
+/// Serializes a map key into the `String` a CFG++ object entry needs.
+#[cfg(feature = "serde")]
+struct CfgppMapKeySerializer;
+
+#[cfg(feature = "serde")]
+impl Serializer for CfgppMapKeySerializer {
+    type Ok = String;
+    type Error = crate::error::CfgppError;
+
+    type SerializeSeq = serde::ser::Impossible<String, crate::error::CfgppError>;
+    type SerializeTuple = serde::ser::Impossible<String, crate::error::CfgppError>;
+    type SerializeTupleStruct = serde::ser::Impossible<String, crate::error::CfgppError>;
+    type SerializeTupleVariant = serde::ser::Impossible<String, crate::error::CfgppError>;
+    type SerializeMap = serde::ser::Impossible<String, crate::error::CfgppError>;
+    type SerializeStruct = serde::ser::Impossible<String, crate::error::CfgppError>;
+    type SerializeStructVariant = serde::ser::Impossible<String, crate::error::CfgppError>;
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> { Ok(v.to_string()) }
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> { Ok(v.to_string()) }
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> { Ok(v.to_string()) }
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> { Ok(v.to_string()) }
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> { Ok(v.to_string()) }
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> { Ok(v.to_string()) }
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> { Ok(v.to_string()) }
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> { Ok(v.to_string()) }
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> { Ok(v.to_string()) }
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> { Ok(v.to_string()) }
+
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Map keys must be strings or integers, not floats"))
+    }
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Map keys must be strings or integers, not floats"))
+    }
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Map keys must be strings or integers, not bytes"))
+    }
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Map keys cannot be None"))
+    }
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Map keys cannot be unit"))
+    }
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(name.to_string())
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(variant.to_string())
+    }
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(crate::error::CfgppError::parse_error("Map keys cannot be newtype variants"))
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Map keys cannot be sequences"))
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Map keys cannot be tuples"))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Map keys cannot be tuple structs"))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Map keys cannot be tuple variants"))
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Map keys cannot be maps"))
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Map keys cannot be structs"))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Map keys cannot be struct variants"))
+    }
+}
+
+/// Extracts the bare `&str` out of a `CfgppValue::Raw`'s marked newtype
+/// struct, refusing anything else - mirrors `serde_json`'s own private
+/// raw-value extraction serializer.
+#[cfg(feature = "serde")]
+struct CfgppRawTextSerializer;
+
+#[cfg(feature = "serde")]
+impl Serializer for CfgppRawTextSerializer {
+    type Ok = String;
+    type Error = crate::error::CfgppError;
+
+    type SerializeSeq = serde::ser::Impossible<String, crate::error::CfgppError>;
+    type SerializeTuple = serde::ser::Impossible<String, crate::error::CfgppError>;
+    type SerializeTupleStruct = serde::ser::Impossible<String, crate::error::CfgppError>;
+    type SerializeTupleVariant = serde::ser::Impossible<String, crate::error::CfgppError>;
+    type SerializeMap = serde::ser::Impossible<String, crate::error::CfgppError>;
+    type SerializeStruct = serde::ser::Impossible<String, crate::error::CfgppError>;
+    type SerializeStructVariant = serde::ser::Impossible<String, crate::error::CfgppError>;
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(crate::error::CfgppError::parse_error("Raw block text must be a string"))
+    }
+}
+
+#[cfg(feature = "serde")]
+struct CfgppSeqSerializer {
+    items: Vec<CfgppNode>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::SerializeSeq for CfgppSeqSerializer {
+    type Ok = CfgppNode;
+    type Error = crate::error::CfgppError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.items.push(value.serialize(CfgppSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(CfgppNode::Array(self.items))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::SerializeTuple for CfgppSeqSerializer {
+    type Ok = CfgppNode;
+    type Error = crate::error::CfgppError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        serde::ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        serde::ser::SerializeSeq::end(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::SerializeTupleStruct for CfgppSeqSerializer {
+    type Ok = CfgppNode;
+    type Error = crate::error::CfgppError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        serde::ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        serde::ser::SerializeSeq::end(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct CfgppVariantSeqSerializer {
+    variant: &'static str,
+    items: Vec<CfgppNode>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::SerializeTupleVariant for CfgppVariantSeqSerializer {
+    type Ok = CfgppNode;
+    type Error = crate::error::CfgppError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.items.push(value.serialize(CfgppSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(CfgppNode::Object(vec![(
+            self.variant.to_string(),
+            CfgppNode::Array(self.items),
+        )]))
+    }
+}
+
+#[cfg(feature = "serde")]
+struct CfgppMapSerializer {
+    entries: Vec<(String, CfgppNode)>,
+    next_key: Option<String>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::SerializeMap for CfgppMapSerializer {
+    type Ok = CfgppNode;
+    type Error = crate::error::CfgppError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.next_key = Some(key.serialize(CfgppMapKeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = self
+            .next_key
+            .take()
+            .expect("serialize_value called before serialize_key");
+        self.entries.push((key, value.serialize(CfgppSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(CfgppNode::Object(self.entries))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::SerializeStruct for CfgppMapSerializer {
+    type Ok = CfgppNode;
+    type Error = crate::error::CfgppError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.entries.push((key.to_string(), value.serialize(CfgppSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(CfgppNode::Object(self.entries))
+    }
+}
+
+#[cfg(feature = "serde")]
+struct CfgppVariantMapSerializer {
+    variant: &'static str,
+    entries: Vec<(String, CfgppNode)>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::SerializeStructVariant for CfgppVariantMapSerializer {
+    type Ok = CfgppNode;
+    type Error = crate::error::CfgppError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.entries.push((key.to_string(), value.serialize(CfgppSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(CfgppNode::Object(vec![(
+            self.variant.to_string(),
+            CfgppNode::Object(self.entries),
+        )]))
+    }
+}
+
+/// Render a scalar (or array/inline-object) fragment with no trailing
+/// punctuation or indentation of its own - used both for `key = value;`
+/// assignments and for elements nested inside an array.
+#[cfg(feature = "serde")]
+fn render_scalar(node: &CfgppNode, out: &mut String) {
+    match node {
+        CfgppNode::Null => out.push_str("null"),
+        CfgppNode::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        CfgppNode::Int(i) => out.push_str(&i.to_string()),
+        CfgppNode::UInt(u) => out.push_str(&u.to_string()),
+        CfgppNode::Float(f) => out.push_str(&f.to_string()),
+        CfgppNode::EnumIdent(e) => out.push_str(e),
+        CfgppNode::RawText(text) => out.push_str(text),
+        CfgppNode::Str(s) => {
+            out.push('"');
+            for ch in s.chars() {
+                match ch {
+                    '"' => out.push_str("\\\""),
+                    '\\' => out.push_str("\\\\"),
+                    '\n' => out.push_str("\\n"),
+                    '\r' => out.push_str("\\r"),
+                    '\t' => out.push_str("\\t"),
+                    _ => out.push(ch),
+                }
+            }
+            out.push('"');
+        }
+        CfgppNode::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                render_scalar(item, out);
+            }
+            out.push(']');
+        }
+        CfgppNode::Object(entries) => {
+            out.push_str("{ ");
+            for (key, value) in entries {
+                out.push_str(key);
+                out.push_str(" = ");
+                render_scalar(value, out);
+                out.push_str("; ");
+            }
+            out.push('}');
+        }
+    }
+}
+
+/// Render a sequence of object entries, one `key = value;` or `key { ... }`
+/// block per entry. `pretty` controls whether entries get newlines and
+/// `indent`-deep indentation, or are packed onto as few lines as possible.
+#[cfg(feature = "serde")]
+fn render_entries(entries: &[(String, CfgppNode)], indent: usize, pretty: bool, out: &mut String) {
+    for (key, value) in entries {
+        if pretty {
+            out.push_str(&"    ".repeat(indent));
+        }
+        out.push_str(key);
+
+        match value {
+            CfgppNode::Object(inner) => {
+                out.push_str(if pretty { " {\n" } else { " { " });
+                render_entries(inner, indent + 1, pretty, out);
+                if pretty {
+                    out.push_str(&"    ".repeat(indent));
+                    out.push_str("}\n");
+                } else {
+                    out.push_str("} ");
+                }
+            }
+            other => {
+                out.push_str(" = ");
+                render_scalar(other, out);
+                out.push_str(if pretty { ";\n" } else { "; " });
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn render_root(node: CfgppNode, pretty: bool) -> String {
+    let mut out = String::new();
+    match node {
+        CfgppNode::Object(entries) => render_entries(&entries, 0, pretty, &mut out),
+        other => render_scalar(&other, &mut out),
+    }
+    out
+}
+
+/// Serialize any `Serialize` value to compact CFG++ text.
+#[cfg(feature = "serde")]
+pub fn to_cfgpp<T: Serialize>(value: &T) -> crate::error::CfgppResult<String> {
+    let node = value.serialize(CfgppSerializer)?;
+    Ok(render_root(node, false))
+}
+
+/// Serialize any `Serialize` value to indented, multi-line CFG++ text.
+#[cfg(feature = "serde")]
+pub fn to_cfgpp_pretty<T: Serialize>(value: &T) -> crate::error::CfgppResult<String> {
+    let node = value.serialize(CfgppSerializer)?;
+    Ok(render_root(node, true))
+}
+
 /// Convert CFG++ value to JSON string
 #[cfg(feature = "serde")]
 pub fn to_json(value: &CfgppValue) -> crate::error::CfgppResult<String> {
@@ -241,11 +1295,11 @@ pub fn from_yaml(yaml: &str) -> crate::error::CfgppResult<CfgppValue> {
 #[cfg(feature = "serde")]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use crate::value::CfgppObject;
 
     #[test]
     fn test_json_roundtrip() {
-        let mut obj = HashMap::new();
+        let mut obj = CfgppObject::new();
         obj.insert("name".to_string(), CfgppValue::string("test"));
         obj.insert("value".to_string(), CfgppValue::integer(42));
         obj.insert("enabled".to_string(), CfgppValue::boolean(true));
@@ -279,4 +1333,135 @@ mod tests {
         let json = to_json(&null_value).unwrap();
         assert_eq!(json.trim(), "null");
     }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct ServerConfig {
+        host: String,
+        port: i64,
+        enabled: bool,
+    }
+
+    #[test]
+    fn test_from_value_deserializes_into_struct() {
+        let mut obj = CfgppObject::new();
+        obj.insert("host".to_string(), CfgppValue::string("localhost"));
+        obj.insert("port".to_string(), CfgppValue::integer(5432));
+        obj.insert("enabled".to_string(), CfgppValue::boolean(true));
+        let value = CfgppValue::object_with_values(obj);
+
+        let config: ServerConfig = from_value(&value).unwrap();
+        assert_eq!(
+            config,
+            ServerConfig {
+                host: "localhost".to_string(),
+                port: 5432,
+                enabled: true,
+            }
+        );
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    enum Step {
+        Noop,
+        Filter { field: String },
+    }
+
+    #[test]
+    fn test_from_value_externally_tagged_enum() {
+        let unit_step: Step = from_value(&CfgppValue::enum_value("Noop")).unwrap();
+        assert_eq!(unit_step, Step::Noop);
+
+        let mut variant = CfgppObject::new();
+        let mut fields = CfgppObject::new();
+        fields.insert("field".to_string(), CfgppValue::string("status"));
+        variant.insert("Filter".to_string(), CfgppValue::object_with_values(fields));
+        let struct_step: Step = from_value(&CfgppValue::object_with_values(variant)).unwrap();
+        assert_eq!(
+            struct_step,
+            Step::Filter {
+                field: "status".to_string()
+            }
+        );
+    }
+
+    #[derive(Serialize)]
+    struct Profile {
+        name: String,
+        retries: i32,
+        tags: Vec<String>,
+    }
+
+    #[test]
+    fn test_to_cfgpp_pretty_renders_struct() {
+        let profile = Profile {
+            name: "build".to_string(),
+            retries: 3,
+            tags: vec!["ci".to_string(), "fast".to_string()],
+        };
+
+        let text = to_cfgpp_pretty(&profile).unwrap();
+        assert_eq!(
+            text,
+            "name = \"build\";\nretries = 3;\ntags = [\"ci\", \"fast\"];\n"
+        );
+    }
+
+    #[test]
+    fn test_to_cfgpp_compact_inlines_nested_objects() {
+        let mut inner = CfgppObject::new();
+        inner.insert("host".to_string(), CfgppValue::string("localhost"));
+        let mut outer = CfgppObject::new();
+        outer.insert("database".to_string(), CfgppValue::object_with_values(inner));
+        let value = CfgppValue::object_with_values(outer);
+
+        let text = to_cfgpp(&value).unwrap();
+        assert_eq!(text, "database { host = \"localhost\"; } ");
+    }
+
+    #[test]
+    fn test_to_cfgpp_emits_raw_block_verbatim() {
+        let mut outer = CfgppObject::new();
+        outer.insert(
+            "extra".to_string(),
+            CfgppValue::Raw("{ not , valid ] cfgpp on its own }".to_string()),
+        );
+        let value = CfgppValue::object_with_values(outer);
+
+        let text = to_cfgpp(&value).unwrap();
+        assert_eq!(text, "extra = { not , valid ] cfgpp on its own }; ");
+    }
+
+    #[test]
+    fn test_u64_max_survives_json_roundtrip_without_precision_loss() {
+        let value = CfgppValue::uinteger(u64::MAX);
+
+        let json = to_json_compact(&value).unwrap();
+        assert_eq!(json, "18446744073709551615");
+
+        let reparsed = from_json(&json).unwrap();
+        assert_eq!(reparsed.as_uinteger(), Some(u64::MAX));
+    }
+
+    #[test]
+    fn test_cfgpp_roundtrip_through_parser() {
+        let original = r#"
+        app {
+            host = "localhost";
+            port = 8080;
+            enabled = true;
+        }
+        "#;
+
+        let mut parser = crate::parser::Parser::new();
+        let parsed = parser.parse(original).unwrap();
+
+        let rendered = to_cfgpp_pretty(&parsed).unwrap();
+
+        let mut reparser = crate::parser::Parser::new();
+        let reparsed = reparser
+            .parse(&format!("outer {{\n{}}}\n", rendered))
+            .unwrap();
+
+        assert_eq!(reparsed, parsed);
+    }
 }
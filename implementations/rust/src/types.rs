@@ -0,0 +1,457 @@
+//! Lightweight static type inference and schema conformance checking over
+//! an unevaluated [`AstNode`] tree.
+//!
+//! [`infer_type`] walks an [`AstNode`] and assigns it a [`CfgppType`]
+//! without evaluating it - a `Literal` takes the type of its wrapped
+//! [`CfgppValue`], an `Array` unifies the types of its elements (falling
+//! back to [`CfgppType::Unknown`] if they disagree), an `Object` maps field
+//! names to their inferred field types, and an `Expression` infers the
+//! result type of its operator (a comparison is always `Bool`, arithmetic
+//! promotes to `Float` unless both operands are `Integer`). This lets
+//! [`AstNode::check_types`] catch a schema mismatch - `port` declared an
+//! integer but written as a string - before the tree is ever evaluated,
+//! where [`crate::schema::Schema::validate`] only catches it afterward,
+//! against the materialized [`CfgppValue`].
+
+use crate::ast::{AstNode, BinaryOperator};
+use crate::schema::{Schema, TypeDefinition};
+use crate::value::CfgppValue;
+use std::collections::HashMap;
+use std::fmt;
+
+/// The type [`infer_type`] assigns to an [`AstNode`] without evaluating it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CfgppType {
+    Integer,
+    Float,
+    Bool,
+    String,
+    Null,
+    Array(Box<CfgppType>),
+    Object(HashMap<String, CfgppType>),
+    /// An enum literal, named after the variant it carries rather than the
+    /// enum's declared name - [`AstNode`] doesn't know which `EnumDef` a
+    /// bare `Literal(CfgppValue::Enum(variant))` belongs to. See
+    /// [`AstNode::validate`] for the separate check that a variant actually
+    /// belongs to some declared enum.
+    Enum(String),
+    /// An `Array` whose elements don't all infer to the same [`CfgppType`],
+    /// or an `Expression` whose operands don't unify under its operator.
+    Unknown,
+}
+
+impl fmt::Display for CfgppType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer => write!(f, "integer"),
+            Self::Float => write!(f, "float"),
+            Self::Bool => write!(f, "bool"),
+            Self::String => write!(f, "string"),
+            Self::Null => write!(f, "null"),
+            Self::Array(inner) => write!(f, "array<{}>", inner),
+            Self::Object(fields) => {
+                let mut names: Vec<&str> = fields.keys().map(String::as_str).collect();
+                names.sort_unstable();
+                write!(f, "object{{{}}}", names.join(", "))
+            }
+            Self::Enum(variant) => write!(f, "enum({})", variant),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// A mismatch found by [`AstNode::check_types`] between the [`CfgppType`]
+/// inferred for a node and what the schema declared at that field path
+/// (e.g. `database.port`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeError {
+    /// Dotted/indexed field path of the offending node, e.g. `$.database.port`.
+    pub path: String,
+    /// Debug rendering of the schema's [`TypeDefinition`] at this path.
+    pub expected: String,
+    pub found: CfgppType,
+}
+
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: expected {}, found {}", self.path, self.expected, self.found)
+    }
+}
+
+impl std::error::Error for TypeError {}
+
+impl AstNode {
+    /// Infer a [`CfgppType`] for this node and every node it reaches through
+    /// `schema`'s root type, collecting every mismatch instead of stopping
+    /// at the first. Returns `Ok(())` if `schema` has no root type set, or
+    /// if every field this tree defines that's also named in the schema
+    /// conforms to its declared type. Fields present in one but not the
+    /// other aren't reported here - that's [`crate::schema::Schema::validate`]'s
+    /// job, once the tree has been evaluated into a [`CfgppValue`].
+    pub fn check_types(&self, schema: &Schema) -> Result<(), Vec<TypeError>> {
+        let mut errors = Vec::new();
+        if let Some(root_type) = schema.root_type() {
+            check_node(self, root_type, schema, "$", &mut errors);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Infer the [`CfgppType`] of `node` by walking its shape. Doesn't evaluate
+/// `Expression`s beyond determining their result type, and doesn't resolve
+/// `EnvVar`/`Include`/`Namespace` references.
+pub fn infer_type(node: &AstNode) -> CfgppType {
+    match node {
+        AstNode::Literal { value, .. } => type_of_value(value),
+
+        AstNode::Array { elements, .. } => unify_array(elements.iter().map(infer_type)),
+
+        AstNode::Object { data, .. } => {
+            CfgppType::Object(data.fields.iter().map(|(key, value)| (key.clone(), infer_type(value))).collect())
+        }
+
+        AstNode::Root { objects, .. } => {
+            let mut fields = HashMap::new();
+            for object in objects {
+                if let Some(key) = top_level_key(object) {
+                    fields.insert(key.to_string(), infer_type(object));
+                }
+            }
+            CfgppType::Object(fields)
+        }
+
+        AstNode::Assignment { value, .. } => infer_type(value),
+
+        AstNode::Expression { data, .. } => infer_expression(&data.operator, &data.left, &data.right),
+
+        AstNode::EnumDef { name, .. } => CfgppType::Enum(name.clone()),
+
+        AstNode::EnvVar { .. } => CfgppType::String,
+
+        AstNode::Include { .. } | AstNode::Namespace { .. } => CfgppType::Unknown,
+    }
+}
+
+fn top_level_key(node: &AstNode) -> Option<&str> {
+    match node {
+        AstNode::Object { data, .. } => data.name.as_deref(),
+        AstNode::Assignment { key, .. } => Some(key.as_str()),
+        _ => None,
+    }
+}
+
+fn type_of_value(value: &CfgppValue) -> CfgppType {
+    match value {
+        CfgppValue::Null => CfgppType::Null,
+        CfgppValue::Boolean(_) => CfgppType::Bool,
+        CfgppValue::Integer(_) | CfgppValue::UInteger(_) | CfgppValue::SizedInteger { .. } => CfgppType::Integer,
+        #[cfg(feature = "arbitrary_precision")]
+        CfgppValue::BigNumber(_) => CfgppType::Integer,
+        CfgppValue::Double(_) => CfgppType::Float,
+        CfgppValue::String(_) | CfgppValue::Raw(_) => CfgppType::String,
+        CfgppValue::Enum(variant) => CfgppType::Enum(variant.clone()),
+        CfgppValue::Array(elements) => unify_array(elements.iter().map(type_of_value)),
+        CfgppValue::Object(object) => {
+            CfgppType::Object(object.iter().map(|(key, value)| (key.clone(), type_of_value(value))).collect())
+        }
+    }
+}
+
+/// Unify a sequence of inferred element types into one [`CfgppType::Array`],
+/// or [`CfgppType::Unknown`] if they disagree. An empty array unifies to
+/// `Array(Unknown)` since there's nothing to disagree about.
+fn unify_array(mut types: impl Iterator<Item = CfgppType>) -> CfgppType {
+    let Some(first) = types.next() else {
+        return CfgppType::Array(Box::new(CfgppType::Unknown));
+    };
+
+    if types.all(|element| element == first) {
+        CfgppType::Array(Box::new(first))
+    } else {
+        CfgppType::Unknown
+    }
+}
+
+fn is_numeric(ty: &CfgppType) -> bool {
+    matches!(ty, CfgppType::Integer | CfgppType::Float)
+}
+
+/// Infer the result type of a [`BinaryOperator`] applied to `left`/`right`,
+/// without evaluating either operand.
+fn infer_expression(operator: &BinaryOperator, left: &AstNode, right: &AstNode) -> CfgppType {
+    match operator {
+        BinaryOperator::Eq
+        | BinaryOperator::Ne
+        | BinaryOperator::Gt
+        | BinaryOperator::Lt
+        | BinaryOperator::Ge
+        | BinaryOperator::Le
+        | BinaryOperator::And
+        | BinaryOperator::Or => CfgppType::Bool,
+
+        BinaryOperator::Coalesce => {
+            let left = infer_type(left);
+            if left == CfgppType::Null {
+                infer_type(right)
+            } else {
+                left
+            }
+        }
+
+        BinaryOperator::Add
+        | BinaryOperator::Subtract
+        | BinaryOperator::Multiply
+        | BinaryOperator::Divide
+        | BinaryOperator::Modulo
+        | BinaryOperator::Power => {
+            let (left, right) = (infer_type(left), infer_type(right));
+            if matches!(operator, BinaryOperator::Add) && left == CfgppType::String && right == CfgppType::String {
+                CfgppType::String
+            } else if left == CfgppType::Integer && right == CfgppType::Integer {
+                CfgppType::Integer
+            } else if is_numeric(&left) && is_numeric(&right) {
+                CfgppType::Float
+            } else {
+                CfgppType::Unknown
+            }
+        }
+    }
+}
+
+/// True if `found` satisfies the schema's `expected` type definition.
+/// `Double` accepts `Integer` too, mirroring how [`crate::ast::eval_binary`]
+/// only promotes an integer expression to a float when the operation needs
+/// one.
+fn type_def_matches(found: &CfgppType, expected: &TypeDefinition) -> bool {
+    match expected {
+        TypeDefinition::Null => *found == CfgppType::Null,
+        TypeDefinition::Boolean => *found == CfgppType::Bool,
+        TypeDefinition::Integer => *found == CfgppType::Integer,
+        TypeDefinition::Double => matches!(found, CfgppType::Float | CfgppType::Integer),
+        TypeDefinition::String => *found == CfgppType::String,
+        TypeDefinition::Enum(_) => matches!(found, CfgppType::Enum(_)),
+        TypeDefinition::Array(inner) => match found {
+            CfgppType::Array(found_inner) => type_def_matches(found_inner, inner),
+            _ => false,
+        },
+        TypeDefinition::Tuple(_) => matches!(found, CfgppType::Array(_)),
+        TypeDefinition::Object(_) => matches!(found, CfgppType::Object(_)),
+        TypeDefinition::Union(types) | TypeDefinition::OneOf(types) => {
+            types.iter().any(|candidate| type_def_matches(found, candidate))
+        }
+        TypeDefinition::Optional(inner) => *found == CfgppType::Null || type_def_matches(found, inner),
+    }
+}
+
+fn check_node(node: &AstNode, expected: &TypeDefinition, schema: &Schema, path: &str, errors: &mut Vec<TypeError>) {
+    if let AstNode::Assignment { value, .. } = node {
+        return check_node(value, expected, schema, path, errors);
+    }
+
+    match expected {
+        TypeDefinition::Optional(inner) => check_node(node, inner, schema, path, errors),
+
+        TypeDefinition::Object(schema_name) => match node {
+            AstNode::Root { objects, .. } => check_root_fields(objects, schema_name, schema, path, errors),
+            AstNode::Object { data, .. } => check_object_fields(&data.fields, schema_name, schema, path, errors),
+            _ => check_leaf(node, expected, path, errors),
+        },
+
+        TypeDefinition::Array(element_type) => match node {
+            AstNode::Array { elements, .. } => {
+                for (i, element) in elements.iter().enumerate() {
+                    check_node(element, element_type, schema, &format!("{}[{}]", path, i), errors);
+                }
+            }
+            _ => check_leaf(node, expected, path, errors),
+        },
+
+        _ => check_leaf(node, expected, path, errors),
+    }
+}
+
+fn check_leaf(node: &AstNode, expected: &TypeDefinition, path: &str, errors: &mut Vec<TypeError>) {
+    let found = infer_type(node);
+    if !type_def_matches(&found, expected) {
+        errors.push(TypeError { path: path.to_string(), expected: format!("{:?}", expected), found });
+    }
+}
+
+fn check_object_fields(
+    fields: &HashMap<String, AstNode>,
+    schema_name: &str,
+    schema: &Schema,
+    path: &str,
+    errors: &mut Vec<TypeError>,
+) {
+    let Some(field_defs) = schema.object_fields(schema_name) else {
+        return;
+    };
+
+    for (key, node) in fields {
+        if let Some(field_def) = field_defs.get(key) {
+            check_node(node, &field_def.field_type, schema, &format!("{}.{}", path, key), errors);
+        }
+    }
+}
+
+fn check_root_fields(objects: &[AstNode], schema_name: &str, schema: &Schema, path: &str, errors: &mut Vec<TypeError>) {
+    let Some(field_defs) = schema.object_fields(schema_name) else {
+        return;
+    };
+
+    for object in objects {
+        let Some(key) = top_level_key(object) else {
+            continue;
+        };
+        if let Some(field_def) = field_defs.get(key) {
+            check_node(object, &field_def.field_type, schema, &format!("{}.{}", path, key), errors);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::FieldDefinition;
+
+    #[test]
+    fn test_infer_type_of_literals() {
+        assert_eq!(infer_type(&AstNode::literal(CfgppValue::integer(1))), CfgppType::Integer);
+        assert_eq!(infer_type(&AstNode::literal(CfgppValue::Double(1.5))), CfgppType::Float);
+        assert_eq!(infer_type(&AstNode::literal(CfgppValue::string("x"))), CfgppType::String);
+        assert_eq!(infer_type(&AstNode::literal(CfgppValue::boolean(true))), CfgppType::Bool);
+        assert_eq!(infer_type(&AstNode::literal(CfgppValue::null())), CfgppType::Null);
+    }
+
+    #[test]
+    fn test_infer_type_unifies_uniform_array_elements() {
+        let array = AstNode::array(vec![
+            AstNode::literal(CfgppValue::integer(1)),
+            AstNode::literal(CfgppValue::integer(2)),
+        ]);
+        assert_eq!(infer_type(&array), CfgppType::Array(Box::new(CfgppType::Integer)));
+    }
+
+    #[test]
+    fn test_infer_type_is_unknown_for_mismatched_array_elements() {
+        let array = AstNode::array(vec![
+            AstNode::literal(CfgppValue::integer(1)),
+            AstNode::literal(CfgppValue::string("two")),
+        ]);
+        assert_eq!(infer_type(&array), CfgppType::Unknown);
+    }
+
+    #[test]
+    fn test_infer_type_maps_object_fields() {
+        let mut fields = HashMap::new();
+        fields.insert("port".to_string(), AstNode::literal(CfgppValue::integer(5432)));
+        let object = AstNode::object(None, fields);
+
+        let CfgppType::Object(types) = infer_type(&object) else {
+            panic!("expected an Object type");
+        };
+        assert_eq!(types.get("port"), Some(&CfgppType::Integer));
+    }
+
+    #[test]
+    fn test_infer_type_of_arithmetic_expression() {
+        let expr = AstNode::expression(
+            BinaryOperator::Add,
+            AstNode::literal(CfgppValue::integer(1)),
+            AstNode::literal(CfgppValue::integer(2)),
+        );
+        assert_eq!(infer_type(&expr), CfgppType::Integer);
+
+        let mixed = AstNode::expression(
+            BinaryOperator::Add,
+            AstNode::literal(CfgppValue::integer(1)),
+            AstNode::literal(CfgppValue::Double(0.5)),
+        );
+        assert_eq!(infer_type(&mixed), CfgppType::Float);
+    }
+
+    #[test]
+    fn test_infer_type_of_comparison_expression_is_bool() {
+        let expr = AstNode::expression(
+            BinaryOperator::Gt,
+            AstNode::literal(CfgppValue::integer(1)),
+            AstNode::literal(CfgppValue::integer(2)),
+        );
+        assert_eq!(infer_type(&expr), CfgppType::Bool);
+    }
+
+    #[test]
+    fn test_check_types_passes_when_fields_match_schema() {
+        let mut fields = HashMap::new();
+        fields.insert("port".to_string(), FieldDefinition::new(TypeDefinition::Integer, true));
+        fields.insert("host".to_string(), FieldDefinition::new(TypeDefinition::String, true));
+
+        let mut schema = Schema::new();
+        schema.add_object_schema("database".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("database".to_string()));
+
+        let root = AstNode::root(vec![
+            AstNode::assignment("port".to_string(), AstNode::literal(CfgppValue::integer(5432))),
+            AstNode::assignment("host".to_string(), AstNode::literal(CfgppValue::string("localhost"))),
+        ]);
+
+        assert!(root.check_types(&schema).is_ok());
+    }
+
+    #[test]
+    fn test_check_types_reports_mismatched_field_type() {
+        let mut fields = HashMap::new();
+        fields.insert("port".to_string(), FieldDefinition::new(TypeDefinition::Integer, true));
+
+        let mut schema = Schema::new();
+        schema.add_object_schema("database".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("database".to_string()));
+
+        let root = AstNode::root(vec![AstNode::assignment(
+            "port".to_string(),
+            AstNode::literal(CfgppValue::string("5432")),
+        )]);
+
+        let errors = root.check_types(&schema).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "$.port");
+        assert_eq!(errors[0].found, CfgppType::String);
+    }
+
+    #[test]
+    fn test_check_types_checks_array_elements_against_their_element_type() {
+        let mut fields = HashMap::new();
+        fields.insert(
+            "hosts".to_string(),
+            FieldDefinition::new(TypeDefinition::Array(Box::new(TypeDefinition::String)), true),
+        );
+
+        let mut schema = Schema::new();
+        schema.add_object_schema("database".to_string(), fields);
+        schema.set_root_schema(TypeDefinition::Object("database".to_string()));
+
+        let root = AstNode::root(vec![AstNode::assignment(
+            "hosts".to_string(),
+            AstNode::array(vec![
+                AstNode::literal(CfgppValue::string("a")),
+                AstNode::literal(CfgppValue::integer(1)),
+            ]),
+        )]);
+
+        let errors = root.check_types(&schema).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "$.hosts[1]");
+    }
+
+    #[test]
+    fn test_check_types_is_ok_without_a_root_schema() {
+        let root = AstNode::root(vec![]);
+        assert!(root.check_types(&Schema::new()).is_ok());
+    }
+}
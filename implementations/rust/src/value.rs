@@ -7,6 +7,16 @@ use crate::error::{CfgppError, CfgppResult};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+/// Backing map for [`CfgppValue::Object`]. A plain `HashMap` by default; with
+/// the `preserve_order` feature enabled, an `IndexMap` that keeps keys in
+/// insertion order from the parser through to serialization.
+#[cfg(not(feature = "preserve_order"))]
+pub type CfgppObject = HashMap<String, CfgppValue>;
+
+/// Backing map for [`CfgppValue::Object`], preserving insertion order.
+#[cfg(feature = "preserve_order")]
+pub type CfgppObject = indexmap::IndexMap<String, CfgppValue>;
+
 /// Represents a CFG++ value - the core data type
 #[derive(Debug, Clone, PartialEq)]
 
@@ -17,6 +27,30 @@ pub enum CfgppValue {
     Boolean(bool),
     /// Integer value (i64 for maximum compatibility)
     Integer(i64),
+    /// An integer literal carrying an explicit width/signedness suffix
+    /// (`5432u16`, `255u8`, `127i8`, ...), produced when the lexer recognizes
+    /// an `[iu](8|16|32|64)` suffix on a numeric literal. A leading `-` is
+    /// lexed as a separate token, so no negative suffixed literal is parsed
+    /// into this variant. `value` always
+    /// holds the literal's exact bits reinterpreted as `i64` - for unsigned
+    /// widths this is a bitcast, not a numeric cast, so recovering the
+    /// original magnitude for an unsigned field means reading it back as
+    /// `value as u64` (see [`CfgppValue::as_sized_integer`]), which round-trips
+    /// losslessly for every width up to 64 bits.
+    SizedInteger {
+        value: i64,
+        bits: u8,
+        signed: bool,
+    },
+    /// Unsigned integer literal too large to fit in `i64` (between
+    /// `i64::MAX + 1` and `u64::MAX`), kept exact rather than losing
+    /// precision by widening to `Double`.
+    UInteger(u64),
+    /// Integer literal too large to fit even in `u64`, kept as its exact
+    /// decimal digits rather than losing precision. Only produced by the
+    /// parser/deserializer when the `arbitrary_precision` feature is enabled.
+    #[cfg(feature = "arbitrary_precision")]
+    BigNumber(String),
     /// Floating-point value
     Double(f64),
     /// String value
@@ -26,7 +60,12 @@ pub enum CfgppValue {
     /// Array of values
     Array(Vec<CfgppValue>),
     /// Object (key-value pairs)
-    Object(HashMap<String, CfgppValue>),
+    Object(CfgppObject),
+    /// An object or array block whose contents were not parsed eagerly -
+    /// the exact source text of the block (including its delimiters), kept
+    /// verbatim until [`CfgppValue::force`] materializes it. Produced only
+    /// by a [`crate::parser::Parser`] with `ParserOptions::lazy` enabled.
+    Raw(String),
 }
 
 impl CfgppValue {
@@ -45,6 +84,27 @@ impl CfgppValue {
         Self::Integer(value)
     }
 
+    /// Create a new unsigned integer value (for literals beyond `i64::MAX`)
+    pub fn uinteger(value: u64) -> Self {
+        Self::UInteger(value)
+    }
+
+    /// Create a new width/signedness-annotated integer value, as produced by
+    /// a suffixed literal like `255u8` or `127i8`. `value` is the literal's
+    /// bits reinterpreted as `i64` (see the variant's doc comment); this
+    /// constructor does not itself validate that `value` fits in `bits` -
+    /// the lexer/parser do that when a literal's suffix is recognized.
+    pub fn sized_integer(value: i64, bits: u8, signed: bool) -> Self {
+        Self::SizedInteger { value, bits, signed }
+    }
+
+    /// Create a new arbitrary-precision integer value from its exact decimal
+    /// digits (for literals beyond `u64::MAX`)
+    #[cfg(feature = "arbitrary_precision")]
+    pub fn big_number(digits: impl Into<String>) -> Self {
+        Self::BigNumber(digits.into())
+    }
+
     /// Create a new double value
     pub fn double(value: f64) -> Self {
         Self::Double(value)
@@ -72,11 +132,11 @@ impl CfgppValue {
 
     /// Create a new empty object
     pub fn object() -> Self {
-        Self::Object(HashMap::new())
+        Self::Object(CfgppObject::new())
     }
 
     /// Create a new object with values
-    pub fn object_with_values(values: HashMap<String, CfgppValue>) -> Self {
+    pub fn object_with_values(values: CfgppObject) -> Self {
         Self::Object(values)
     }
 
@@ -86,11 +146,16 @@ impl CfgppValue {
             Self::Null => "null",
             Self::Boolean(_) => "boolean",
             Self::Integer(_) => "integer",
+            Self::UInteger(_) => "uinteger",
+            Self::SizedInteger { .. } => "sized_integer",
+            #[cfg(feature = "arbitrary_precision")]
+            Self::BigNumber(_) => "big_number",
             Self::Double(_) => "double",
             Self::String(_) => "string",
             Self::Enum(_) => "enum",
             Self::Array(_) => "array",
             Self::Object(_) => "object",
+            Self::Raw(_) => "raw",
         }
     }
 
@@ -109,6 +174,22 @@ impl CfgppValue {
         matches!(self, Self::Integer(_))
     }
 
+    /// Check if this value is an unsigned integer beyond `i64::MAX`
+    pub fn is_uinteger(&self) -> bool {
+        matches!(self, Self::UInteger(_))
+    }
+
+    /// Check if this value is a width/signedness-annotated integer literal
+    pub fn is_sized_integer(&self) -> bool {
+        matches!(self, Self::SizedInteger { .. })
+    }
+
+    /// Check if this value is an arbitrary-precision integer beyond `u64::MAX`
+    #[cfg(feature = "arbitrary_precision")]
+    pub fn is_big_number(&self) -> bool {
+        matches!(self, Self::BigNumber(_))
+    }
+
     /// Check if this value is a double
     pub fn is_double(&self) -> bool {
         matches!(self, Self::Double(_))
@@ -134,6 +215,11 @@ impl CfgppValue {
         matches!(self, Self::Object(_))
     }
 
+    /// Check if this value is an un-forced raw block
+    pub fn is_raw(&self) -> bool {
+        matches!(self, Self::Raw(_))
+    }
+
     /// Get this value as a boolean, if possible
     pub fn as_boolean(&self) -> Option<bool> {
         match self {
@@ -150,6 +236,33 @@ impl CfgppValue {
         }
     }
 
+    /// Get this value as an unsigned integer, if possible
+    pub fn as_uinteger(&self) -> Option<u64> {
+        match self {
+            Self::UInteger(u) => Some(*u),
+            _ => None,
+        }
+    }
+
+    /// Get this value's `(value, bits, signed)`, if it is a sized integer
+    /// literal. For `signed: false`, reinterpret `value as u64` to recover
+    /// the literal's original magnitude.
+    pub fn as_sized_integer(&self) -> Option<(i64, u8, bool)> {
+        match self {
+            Self::SizedInteger { value, bits, signed } => Some((*value, *bits, *signed)),
+            _ => None,
+        }
+    }
+
+    /// Get this value's digits, if it is an arbitrary-precision integer
+    #[cfg(feature = "arbitrary_precision")]
+    pub fn as_big_number(&self) -> Option<&str> {
+        match self {
+            Self::BigNumber(digits) => Some(digits),
+            _ => None,
+        }
+    }
+
     /// Get this value as a double, if possible
     pub fn as_double(&self) -> Option<f64> {
         match self {
@@ -183,7 +296,7 @@ impl CfgppValue {
     }
 
     /// Get this value as an object, if possible
-    pub fn as_object(&self) -> Option<&HashMap<String, CfgppValue>> {
+    pub fn as_object(&self) -> Option<&CfgppObject> {
         match self {
             Self::Object(obj) => Some(obj),
             _ => None,
@@ -191,18 +304,48 @@ impl CfgppValue {
     }
 
     /// Get this value as a mutable object, if possible
-    pub fn as_object_mut(&mut self) -> Option<&mut HashMap<String, CfgppValue>> {
+    pub fn as_object_mut(&mut self) -> Option<&mut CfgppObject> {
         match self {
             Self::Object(obj) => Some(obj),
             _ => None,
         }
     }
 
+    /// Get the original, un-forced source text of a raw block, if this is one
+    pub fn as_raw(&self) -> Option<&str> {
+        match self {
+            Self::Raw(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Materialize a raw block by parsing its stored source text. Returns
+    /// `self` cloned unchanged if this isn't a `Raw` value.
+    pub fn force(&self) -> CfgppResult<CfgppValue> {
+        match self {
+            Self::Raw(text) => crate::parser::Parser::new().parse(text),
+            other => Ok(other.clone()),
+        }
+    }
+
     /// Get a value by key (for objects)
     pub fn get(&self, key: &str) -> Option<&CfgppValue> {
         self.as_object()?.get(key)
     }
 
+    /// Get a value by key, or a `KeyNotFound` error suggesting the closest
+    /// sibling key (by edit distance) if the exact key isn't present
+    pub fn get_checked(&self, key: &str) -> CfgppResult<&CfgppValue> {
+        let object = self
+            .as_object()
+            .ok_or_else(|| CfgppError::type_error("object", self.type_name()))?;
+
+        object.get(key).ok_or_else(|| {
+            let candidates: Vec<String> = object.keys().cloned().collect();
+            CfgppError::key_not_found_with_candidates(key, &candidates)
+        })
+    }
+
     /// Get a mutable value by key (for objects)
     pub fn get_mut(&mut self, key: &str) -> Option<&mut CfgppValue> {
         self.as_object_mut()?.get_mut(key)
@@ -242,6 +385,39 @@ impl CfgppValue {
         Some(current)
     }
 
+    /// Get a value by path, like [`CfgppValue::get_path`], but transparently
+    /// `force`s any `Raw` block the path traverses through (including a `Raw`
+    /// value at the very end of the path). Returns an owned value rather than
+    /// a reference, since a forced block doesn't live inside `self`.
+    pub fn get_path_owned(&self, path: &str) -> CfgppResult<CfgppValue> {
+        let mut current = self.force()?;
+
+        for part in path.split('.') {
+            if part.contains('[') && part.ends_with(']') {
+                let bracket_pos = part
+                    .find('[')
+                    .ok_or_else(|| CfgppError::key_not_found(part))?;
+                let field = &part[..bracket_pos];
+                let index_str = &part[bracket_pos + 1..part.len() - 1];
+                let index: usize = index_str
+                    .parse()
+                    .map_err(|_| CfgppError::key_not_found(part))?;
+
+                if !field.is_empty() {
+                    current = current.get_checked(field)?.force()?;
+                }
+                current = current
+                    .get_index(index)
+                    .ok_or_else(|| CfgppError::index_out_of_bounds(index))?
+                    .force()?;
+            } else {
+                current = current.get_checked(part)?.force()?;
+            }
+        }
+
+        Ok(current)
+    }
+
     /// Set a value by key (for objects)
     pub fn set(&mut self, key: impl Into<String>, value: CfgppValue) -> CfgppResult<()> {
         match self {
@@ -303,6 +479,11 @@ impl fmt::Display for CfgppValue {
             Self::Null => write!(f, "null"),
             Self::Boolean(b) => write!(f, "{}", b),
             Self::Integer(i) => write!(f, "{}", i),
+            Self::UInteger(u) => write!(f, "{}", u),
+            Self::SizedInteger { value, signed: true, .. } => write!(f, "{}", value),
+            Self::SizedInteger { value, signed: false, .. } => write!(f, "{}", *value as u64),
+            #[cfg(feature = "arbitrary_precision")]
+            Self::BigNumber(digits) => write!(f, "{}", digits),
             Self::Double(d) => write!(f, "{}", d),
             Self::String(s) => write!(f, "\"{}\"", s),
             Self::Enum(e) => write!(f, "{}", e),
@@ -326,6 +507,9 @@ impl fmt::Display for CfgppValue {
                 }
                 write!(f, "}}")
             }
+            // Emitted verbatim: an un-forced block must render as the exact
+            // bytes the parser skipped over, not a re-serialized structure.
+            Self::Raw(text) => write!(f, "{}", text),
         }
     }
 }
@@ -349,6 +533,45 @@ impl From<i64> for CfgppValue {
     }
 }
 
+impl From<u64> for CfgppValue {
+    fn from(value: u64) -> Self {
+        match i64::try_from(value) {
+            Ok(i) => Self::Integer(i),
+            Err(_) => Self::UInteger(value),
+        }
+    }
+}
+
+impl From<i8> for CfgppValue {
+    fn from(value: i8) -> Self {
+        Self::SizedInteger { value: value as i64, bits: 8, signed: true }
+    }
+}
+
+impl From<i16> for CfgppValue {
+    fn from(value: i16) -> Self {
+        Self::SizedInteger { value: value as i64, bits: 16, signed: true }
+    }
+}
+
+impl From<u8> for CfgppValue {
+    fn from(value: u8) -> Self {
+        Self::SizedInteger { value: value as i64, bits: 8, signed: false }
+    }
+}
+
+impl From<u16> for CfgppValue {
+    fn from(value: u16) -> Self {
+        Self::SizedInteger { value: value as i64, bits: 16, signed: false }
+    }
+}
+
+impl From<u32> for CfgppValue {
+    fn from(value: u32) -> Self {
+        Self::SizedInteger { value: value as i64, bits: 32, signed: false }
+    }
+}
+
 impl From<f32> for CfgppValue {
     fn from(value: f32) -> Self {
         Self::Double(value as f64)
@@ -379,8 +602,90 @@ impl From<Vec<CfgppValue>> for CfgppValue {
     }
 }
 
-impl From<HashMap<String, CfgppValue>> for CfgppValue {
-    fn from(value: HashMap<String, CfgppValue>) -> Self {
+impl From<CfgppObject> for CfgppValue {
+    fn from(value: CfgppObject) -> Self {
         Self::Object(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_checked_suggests_typo() {
+        let mut obj = CfgppObject::new();
+        obj.insert("port".to_string(), CfgppValue::integer(5432));
+        obj.insert("host".to_string(), CfgppValue::string("localhost"));
+        let value = CfgppValue::object_with_values(obj);
+
+        let err = value.get_checked("prot").unwrap_err();
+        assert_eq!(err.to_string(), "Key not found: prot (did you mean 'port'?)");
+    }
+
+    #[test]
+    fn test_get_checked_no_suggestion_when_too_far() {
+        let mut obj = CfgppObject::new();
+        obj.insert("port".to_string(), CfgppValue::integer(5432));
+        let value = CfgppValue::object_with_values(obj);
+
+        let err = value.get_checked("completely_unrelated").unwrap_err();
+        assert_eq!(err.to_string(), "Key not found: completely_unrelated");
+    }
+
+    #[test]
+    fn test_raw_force_materializes_and_display_is_verbatim() {
+        let raw = CfgppValue::Raw(r#"{ port = 5432; }"#.to_string());
+        assert!(raw.is_raw());
+        assert_eq!(raw.as_raw(), Some(r#"{ port = 5432; }"#));
+        assert_eq!(raw.to_string(), r#"{ port = 5432; }"#);
+
+        let forced = raw.force().unwrap();
+        assert_eq!(forced.get("port").unwrap().as_integer(), Some(5432));
+    }
+
+    #[test]
+    fn test_uinteger_from_u64_past_i64_max_and_display() {
+        let past_i64_max: u64 = i64::MAX as u64 + 1;
+        let value = CfgppValue::from(past_i64_max);
+        assert!(value.is_uinteger());
+        assert_eq!(value.as_uinteger(), Some(past_i64_max));
+        assert_eq!(value.to_string(), past_i64_max.to_string());
+
+        let fits_i64 = CfgppValue::from(42u64);
+        assert!(fits_i64.is_integer());
+        assert_eq!(fits_i64.as_integer(), Some(42));
+    }
+
+    #[test]
+    fn test_sized_integer_from_impls_and_display() {
+        let small = CfgppValue::from(255u8);
+        assert!(small.is_sized_integer());
+        assert_eq!(small.as_sized_integer(), Some((255, 8, false)));
+        assert_eq!(small.to_string(), "255");
+
+        let negative = CfgppValue::from(-1i8);
+        assert_eq!(negative.as_sized_integer(), Some((-1, 8, true)));
+        assert_eq!(negative.to_string(), "-1");
+
+        let wide_unsigned = CfgppValue::from(40000u16);
+        assert_eq!(wide_unsigned.as_sized_integer(), Some((40000, 16, false)));
+        assert_eq!(wide_unsigned.to_string(), "40000");
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "preserve_order"))]
+    fn test_preserve_order_keeps_insertion_order_through_json_roundtrip() {
+        let mut obj = CfgppObject::new();
+        obj.insert("zebra".to_string(), CfgppValue::integer(1));
+        obj.insert("apple".to_string(), CfgppValue::integer(2));
+        obj.insert("mango".to_string(), CfgppValue::integer(3));
+        let value = CfgppValue::object_with_values(obj);
+
+        let json = value.to_json().unwrap();
+        let roundtripped = CfgppValue::from_json(&json).unwrap();
+
+        let keys: Vec<&String> = roundtripped.as_object().unwrap().keys().collect();
+        assert_eq!(keys, vec!["zebra", "apple", "mango"]);
+    }
+}
@@ -0,0 +1,259 @@
+//! Visitor pattern for traversing and rewriting [`AstNode`] trees.
+//!
+//! Every consumer that wants to walk the tree - collecting `Include` paths,
+//! gathering `EnvVar` names, rewriting `Literal`s - would otherwise have to
+//! hand-roll a recursive match over all ten `AstNode` variants. [`Visitor`]
+//! gives each variant a default method that just recurses into its children,
+//! so an implementor only needs to override the variants it cares about and
+//! drive the traversal with [`walk`]. [`VisitorMut`]/[`walk_mut`] are the
+//! same shape for in-place rewriting.
+
+use crate::ast::{AstNode, BinaryOperator};
+use crate::value::CfgppValue;
+use std::collections::HashMap;
+
+/// Read-only traversal over an [`AstNode`] tree. Each `visit_*` method
+/// defaults to recursing into the node's children via [`walk`] and doing
+/// nothing else - override only the variants you care about.
+pub trait Visitor {
+    fn visit_root(&mut self, objects: &[AstNode]) {
+        for object in objects {
+            walk(object, self);
+        }
+    }
+
+    fn visit_object(&mut self, name: Option<&str>, fields: &HashMap<String, AstNode>) {
+        let _ = name;
+        for node in fields.values() {
+            walk(node, self);
+        }
+    }
+
+    fn visit_array(&mut self, elements: &[AstNode]) {
+        for element in elements {
+            walk(element, self);
+        }
+    }
+
+    fn visit_enum_def(&mut self, name: &str, values: &[String]) {
+        let _ = (name, values);
+    }
+
+    fn visit_include(&mut self, path: &str) {
+        let _ = path;
+    }
+
+    fn visit_env_var(&mut self, name: &str, default: Option<&str>) {
+        let _ = (name, default);
+    }
+
+    fn visit_assignment(&mut self, key: &str, value: &AstNode) {
+        let _ = key;
+        walk(value, self);
+    }
+
+    fn visit_literal(&mut self, value: &CfgppValue) {
+        let _ = value;
+    }
+
+    fn visit_expression(&mut self, operator: &BinaryOperator, left: &AstNode, right: &AstNode) {
+        let _ = operator;
+        walk(left, self);
+        walk(right, self);
+    }
+
+    fn visit_namespace(&mut self, parts: &[String]) {
+        let _ = parts;
+    }
+}
+
+/// Drive `visitor` over `node`, dispatching to the matching `visit_*` method.
+pub fn walk<V: Visitor + ?Sized>(node: &AstNode, visitor: &mut V) {
+    match node {
+        AstNode::Root { objects, .. } => visitor.visit_root(objects),
+        AstNode::Object { data, .. } => visitor.visit_object(data.name.as_deref(), &data.fields),
+        AstNode::Array { elements, .. } => visitor.visit_array(elements),
+        AstNode::EnumDef { name, values, .. } => visitor.visit_enum_def(name, values),
+        AstNode::Include { path, .. } => visitor.visit_include(path),
+        AstNode::EnvVar { name, default, .. } => visitor.visit_env_var(name, default.as_deref()),
+        AstNode::Assignment { key, value, .. } => visitor.visit_assignment(key, value),
+        AstNode::Literal { value, .. } => visitor.visit_literal(value),
+        AstNode::Expression { data, .. } => visitor.visit_expression(&data.operator, &data.left, &data.right),
+        AstNode::Namespace { parts, .. } => visitor.visit_namespace(parts),
+    }
+}
+
+/// In-place rewriting traversal over an [`AstNode`] tree. Each `visit_*`
+/// method defaults to recursing into the node's children via [`walk_mut`]
+/// and doing nothing else - override only the variants you want to rewrite.
+pub trait VisitorMut {
+    fn visit_root(&mut self, objects: &mut [AstNode]) {
+        for object in objects {
+            walk_mut(object, self);
+        }
+    }
+
+    fn visit_object(&mut self, name: Option<&str>, fields: &mut HashMap<String, AstNode>) {
+        let _ = name;
+        for node in fields.values_mut() {
+            walk_mut(node, self);
+        }
+    }
+
+    fn visit_array(&mut self, elements: &mut [AstNode]) {
+        for element in elements {
+            walk_mut(element, self);
+        }
+    }
+
+    fn visit_enum_def(&mut self, name: &str, values: &mut Vec<String>) {
+        let _ = (name, values);
+    }
+
+    fn visit_include(&mut self, path: &mut String) {
+        let _ = path;
+    }
+
+    fn visit_env_var(&mut self, name: &str, default: &mut Option<String>) {
+        let _ = (name, default);
+    }
+
+    fn visit_assignment(&mut self, key: &str, value: &mut AstNode) {
+        let _ = key;
+        walk_mut(value, self);
+    }
+
+    fn visit_literal(&mut self, value: &mut CfgppValue) {
+        let _ = value;
+    }
+
+    fn visit_expression(&mut self, operator: &BinaryOperator, left: &mut AstNode, right: &mut AstNode) {
+        let _ = operator;
+        walk_mut(left, self);
+        walk_mut(right, self);
+    }
+
+    fn visit_namespace(&mut self, parts: &mut Vec<String>) {
+        let _ = parts;
+    }
+}
+
+/// Drive `visitor` over `node`, dispatching to the matching `visit_*` method.
+pub fn walk_mut<V: VisitorMut + ?Sized>(node: &mut AstNode, visitor: &mut V) {
+    match node {
+        AstNode::Root { objects, .. } => visitor.visit_root(objects),
+        AstNode::Object { data, .. } => visitor.visit_object(data.name.as_deref(), &mut data.fields),
+        AstNode::Array { elements, .. } => visitor.visit_array(elements),
+        AstNode::EnumDef { name, values, .. } => visitor.visit_enum_def(name, values),
+        AstNode::Include { path, .. } => visitor.visit_include(path),
+        AstNode::EnvVar { name, default, .. } => visitor.visit_env_var(name, default),
+        AstNode::Assignment { key, value, .. } => visitor.visit_assignment(key, value),
+        AstNode::Literal { value, .. } => visitor.visit_literal(value),
+        AstNode::Expression { data, .. } => visitor.visit_expression(&data.operator, &mut data.left, &mut data.right),
+        AstNode::Namespace { parts, .. } => visitor.visit_namespace(parts),
+    }
+}
+
+/// Collects every [`AstNode::Include`] path in a tree, in traversal order.
+#[derive(Debug, Default)]
+pub struct IncludeCollector {
+    pub paths: Vec<String>,
+}
+
+impl Visitor for IncludeCollector {
+    fn visit_include(&mut self, path: &str) {
+        self.paths.push(path.to_string());
+    }
+}
+
+/// Collects every [`AstNode::EnvVar`] reference in a tree, as `(name,
+/// default)` pairs, in traversal order.
+#[derive(Debug, Default)]
+pub struct EnvVarCollector {
+    pub vars: Vec<(String, Option<String>)>,
+}
+
+impl Visitor for EnvVarCollector {
+    fn visit_env_var(&mut self, name: &str, default: Option<&str>) {
+        self.vars.push((name.to_string(), default.map(|d| d.to_string())));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::BinaryOperator;
+
+    #[test]
+    fn test_include_collector_gathers_paths_in_order() {
+        let root = AstNode::root(vec![
+            AstNode::include("base.cfgpp".to_string()),
+            AstNode::object(
+                Some("db".to_string()),
+                HashMap::from([("url".to_string(), AstNode::include("db.cfgpp".to_string()))]),
+            ),
+        ]);
+
+        let mut collector = IncludeCollector::default();
+        walk(&root, &mut collector);
+
+        assert_eq!(collector.paths, vec!["base.cfgpp".to_string(), "db.cfgpp".to_string()]);
+    }
+
+    #[test]
+    fn test_env_var_collector_gathers_name_default_pairs() {
+        let root = AstNode::root(vec![
+            AstNode::assignment("host".to_string(), AstNode::env_var("HOST".to_string(), None)),
+            AstNode::assignment(
+                "port".to_string(),
+                AstNode::env_var("PORT".to_string(), Some("5432".to_string())),
+            ),
+        ]);
+
+        let mut collector = EnvVarCollector::default();
+        walk(&root, &mut collector);
+
+        assert_eq!(
+            collector.vars,
+            vec![("HOST".to_string(), None), ("PORT".to_string(), Some("5432".to_string()))]
+        );
+    }
+
+    struct LiteralIntDoubler;
+
+    impl VisitorMut for LiteralIntDoubler {
+        fn visit_literal(&mut self, value: &mut CfgppValue) {
+            if let CfgppValue::Integer(i) = value {
+                *i *= 2;
+            }
+        }
+    }
+
+    #[test]
+    fn test_visitor_mut_rewrites_literals_in_place() {
+        let mut node = AstNode::array(vec![
+            AstNode::literal(CfgppValue::integer(1)),
+            AstNode::literal(CfgppValue::integer(2)),
+        ]);
+
+        walk_mut(&mut node, &mut LiteralIntDoubler);
+
+        let value = node.to_value().unwrap();
+        let array = value.as_array().unwrap();
+        assert_eq!(array, &vec![CfgppValue::integer(2), CfgppValue::integer(4)]);
+    }
+
+    #[test]
+    fn test_walk_descends_into_expression_operands() {
+        let expression = AstNode::expression(
+            BinaryOperator::Add,
+            AstNode::include("left.cfgpp".to_string()),
+            AstNode::include("right.cfgpp".to_string()),
+        );
+
+        let mut collector = IncludeCollector::default();
+        walk(&expression, &mut collector);
+
+        assert_eq!(collector.paths, vec!["left.cfgpp".to_string(), "right.cfgpp".to_string()]);
+    }
+}